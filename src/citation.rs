@@ -33,5 +33,25 @@ pub fn render(entry: &bibtex::Entry) -> Option<String> {
     }
 }
 
+/// Renders a short author-year label like `Rivest et al. 1978`.
+#[must_use]
+pub fn render_short(entry: &bibtex::Entry) -> Option<String> {
+    let mut output = String::new();
+    let mut driver = Driver::default();
+    driver.process_short(entry);
+    driver.finish().for_each(|(inline, punct)| {
+        if let Inline::Regular(text) = inline {
+            output.push_str(&text);
+        }
+        output.push_str(punct.as_str());
+    });
+
+    if output.is_empty() {
+        None
+    } else {
+        Some(output.nfc().collect())
+    }
+}
+
 #[cfg(test)]
 mod tests;
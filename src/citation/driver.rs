@@ -1,3 +1,4 @@
+use chrono::Datelike;
 use isocountry::CountryCode;
 use itertools::Itertools;
 use lsp_types::Url;
@@ -9,7 +10,7 @@ use super::{
     entry::{EntryData, EntryKind},
     field::{
         author::AuthorField,
-        date::DateField,
+        date::{DateField, DateFieldData},
         number::{NumberField, NumberFieldData},
         text::TextField,
     },
@@ -147,6 +148,50 @@ impl Driver {
         self.pubstate(&mut entry);
     }
 
+    pub fn process_short(&mut self, entry: &bibtex::Entry) {
+        let mut entry = EntryData::from(entry);
+        self.author_surnames(&mut entry);
+        self.year(&mut entry);
+    }
+
+    fn author_surnames(&mut self, entry: &mut EntryData) -> Option<()> {
+        let author = entry
+            .author
+            .remove(&AuthorField::Author)
+            .or_else(|| entry.author.remove(&AuthorField::Editor))?;
+
+        let text = match author.authors.as_slice() {
+            [] => return None,
+            [name] => name.surname().to_string(),
+            [first, second] => format!("{} and {}", first.surname(), second.surname()),
+            [first, ..] => format!("{} et al.", first.surname()),
+        };
+
+        self.builder
+            .push(Inline::Regular(text), Punct::Nothing, Punct::Space);
+
+        Some(())
+    }
+
+    fn year(&mut self, entry: &mut EntryData) -> Option<()> {
+        let date = [DateField::Date, DateField::Year, DateField::Month]
+            .iter()
+            .filter_map(|field| entry.date.remove(field))
+            .reduce(|a, b| a + b)?;
+
+        let year = match date {
+            DateFieldData::Date(date) => date.year().to_string(),
+            DateFieldData::Year(year) => year.to_string(),
+            DateFieldData::Month(_) => return None,
+            DateFieldData::Other(text) => text,
+        };
+
+        self.builder
+            .push(Inline::Regular(year), Punct::Space, Punct::Nothing);
+
+        Some(())
+    }
+
     fn author(&mut self, entry: &mut EntryData) -> Option<()> {
         let author = entry.author.remove(&AuthorField::Author)?;
         self.builder.push(
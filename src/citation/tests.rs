@@ -349,3 +349,43 @@ fn patent_almendro_1998() {
 }"#
     ));
 }
+
+fn render_short_entry(input: &str) -> Option<String> {
+    let green = parse_bibtex(input);
+    let root = bibtex::Root::cast(bibtex::SyntaxNode::new_root(green)).unwrap();
+    let entry = root.entries().next().unwrap();
+    super::render_short(&entry)
+}
+
+#[test]
+fn short_single_author() {
+    assert_eq!(
+        render_short_entry(r#"@book{knuth, author = {Donald E. Knuth}, year = {1984}}"#),
+        Some(String::from("Knuth 1984"))
+    );
+}
+
+#[test]
+fn short_two_authors() {
+    assert_eq!(
+        render_short_entry(
+            r#"@article{foo, author = {Blom, A. and Jain, B.}, date = {2021-03-04}}"#
+        ),
+        Some(String::from("Blom and Jain 2021"))
+    );
+}
+
+#[test]
+fn short_many_authors() {
+    assert_eq!(
+        render_short_entry(
+            r#"@article{rsa, author = {Rivest, R. L. and Shamir, A. and Adleman, L.}, year = {1978}}"#
+        ),
+        Some(String::from("Rivest et al. 1978"))
+    );
+}
+
+#[test]
+fn short_empty() {
+    assert_eq!(render_short_entry(r#"@misc{foo, title = {Bar}}"#), None);
+}
@@ -1,8 +1,11 @@
 pub mod bib;
+pub mod citations;
+pub mod entries;
+pub mod label;
 pub mod log;
 pub mod tex;
 
-use lsp_types::{DiagnosticSeverity, NumberOrString, Range};
+use lsp_types::{DiagnosticRelatedInformation, DiagnosticSeverity, NumberOrString, Range};
 use rustc_hash::FxHashMap;
 
 use crate::{db::workspace::Workspace, Db};
@@ -15,6 +18,7 @@ pub struct Diagnostic {
     pub range: Range,
     pub code: DiagnosticCode,
     pub message: String,
+    pub related_information: Vec<DiagnosticRelatedInformation>,
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
@@ -22,6 +26,7 @@ pub enum DiagnosticCode {
     Tex(TexCode),
     Bib(BibCode),
     Log(Document),
+    Build,
     Chktex(String),
 }
 
@@ -30,6 +35,10 @@ pub enum TexCode {
     UnexpectedRCurly,
     RCurlyInserted,
     MismatchedEnvironment,
+    UndefinedLabel,
+    DuplicateLabel,
+    UnusedLabel,
+    UndefinedCitation,
 }
 
 impl From<TexCode> for String {
@@ -38,6 +47,10 @@ impl From<TexCode> for String {
             TexCode::UnexpectedRCurly => "Unexpected \"}\"".to_string(),
             TexCode::RCurlyInserted => "Missing \"}\" inserted".to_string(),
             TexCode::MismatchedEnvironment => "Mismatched environment".to_string(),
+            TexCode::UndefinedLabel => "Undefined reference".to_string(),
+            TexCode::DuplicateLabel => "Duplicate label".to_string(),
+            TexCode::UnusedLabel => "Unused label".to_string(),
+            TexCode::UndefinedCitation => "Undefined reference to bibliography entry".to_string(),
         }
     }
 }
@@ -48,6 +61,10 @@ impl From<TexCode> for NumberOrString {
             TexCode::UnexpectedRCurly => NumberOrString::Number(1),
             TexCode::RCurlyInserted => NumberOrString::Number(2),
             TexCode::MismatchedEnvironment => NumberOrString::Number(3),
+            TexCode::UndefinedLabel => NumberOrString::Number(9),
+            TexCode::DuplicateLabel => NumberOrString::Number(10),
+            TexCode::UnusedLabel => NumberOrString::Number(11),
+            TexCode::UndefinedCitation => NumberOrString::Number(12),
         }
     }
 }
@@ -60,6 +77,12 @@ pub enum BibCode {
     ExpectingRCurly,
     ExpectingEq,
     ExpectingFieldValue,
+    UnusedEntry,
+    UnknownEntryType,
+    UnknownField,
+    MissingRequiredField,
+    DuplicateEntry,
+    LikelyDuplicateEntry,
 }
 
 impl From<BibCode> for String {
@@ -70,6 +93,12 @@ impl From<BibCode> for String {
             BibCode::ExpectingRCurly => "Expecting a curly bracket: \"}\"".to_string(),
             BibCode::ExpectingEq => "Expecting an equality sign: \"=\"".to_string(),
             BibCode::ExpectingFieldValue => "Expecting a field value".to_string(),
+            BibCode::UnusedEntry => "Unused entry".to_string(),
+            BibCode::UnknownEntryType => "Unknown entry type".to_string(),
+            BibCode::UnknownField => "Unknown field".to_string(),
+            BibCode::MissingRequiredField => "Missing required field".to_string(),
+            BibCode::DuplicateEntry => "Duplicate entry key".to_string(),
+            BibCode::LikelyDuplicateEntry => "Likely duplicate entry".to_string(),
         }
     }
 }
@@ -82,6 +111,12 @@ impl From<BibCode> for NumberOrString {
             BibCode::ExpectingRCurly => NumberOrString::Number(6),
             BibCode::ExpectingEq => NumberOrString::Number(7),
             BibCode::ExpectingFieldValue => NumberOrString::Number(8),
+            BibCode::UnusedEntry => NumberOrString::Number(13),
+            BibCode::UnknownEntryType => NumberOrString::Number(14),
+            BibCode::UnknownField => NumberOrString::Number(15),
+            BibCode::MissingRequiredField => NumberOrString::Number(16),
+            BibCode::DuplicateEntry => NumberOrString::Number(17),
+            BibCode::LikelyDuplicateEntry => NumberOrString::Number(18),
         }
     }
 }
@@ -96,17 +131,23 @@ pub fn collect(db: &dyn Db, workspace: Workspace) -> FxHashMap<Document, Vec<Dia
                 results.entry(document).or_default().extend(
                     tex::collect(db, document)
                         .iter()
+                        .chain(label::collect(db, workspace, document))
+                        .chain(citations::collect(db, workspace, document))
                         .chain(document.linter(db).chktex(db))
+                        .chain(document.linter(db).build(db))
                         .cloned(),
                 );
             }
             Language::Bib => {
-                results
-                    .entry(document)
-                    .or_default()
-                    .extend(bib::collect(db, document).iter().cloned());
+                results.entry(document).or_default().extend(
+                    bib::collect(db, document)
+                        .iter()
+                        .chain(citations::collect(db, workspace, document))
+                        .chain(entries::collect(db, workspace, document))
+                        .cloned(),
+                );
             }
-            Language::Log => {
+            Language::Log | Language::Blg => {
                 log::collect(db, workspace, document)
                     .iter()
                     .for_each(|(document, diagnostics)| {
@@ -116,10 +157,24 @@ pub fn collect(db: &dyn Db, workspace: Workspace) -> FxHashMap<Document, Vec<Dia
                             .extend(diagnostics.clone());
                     });
             }
-            Language::TexlabRoot | Language::Tectonic => {}
+            Language::TexlabRoot | Language::Tectonic | Language::Latexmkrc => {}
         }
     }
 
+    // The errors of a running build are dropped once the log file reports them as well.
+    for diagnostics in results.values_mut() {
+        let log_diagnostics: Vec<_> = diagnostics
+            .iter()
+            .filter(|diagnostic| matches!(diagnostic.code, DiagnosticCode::Log(_)))
+            .map(|diagnostic| (diagnostic.range, diagnostic.message.clone()))
+            .collect();
+
+        diagnostics.retain(|diagnostic| {
+            diagnostic.code != DiagnosticCode::Build
+                || !log_diagnostics.contains(&(diagnostic.range, diagnostic.message.clone()))
+        });
+    }
+
     results
 }
 
@@ -159,7 +214,7 @@ pub fn collect_filtered(
 
                 let source = match diagnostic.code {
                     DiagnosticCode::Tex(_) | DiagnosticCode::Bib(_) => "texlab",
-                    DiagnosticCode::Log(_) => "latex-build",
+                    DiagnosticCode::Log(_) | DiagnosticCode::Build => "latex-build",
                     DiagnosticCode::Chktex(_) => "chktex",
                 };
 
@@ -167,7 +222,7 @@ pub fn collect_filtered(
                     DiagnosticCode::Tex(code) => Some(code.into()),
                     DiagnosticCode::Bib(code) => Some(code.into()),
                     DiagnosticCode::Chktex(code) => Some(NumberOrString::String(code)),
-                    DiagnosticCode::Log(_) => None,
+                    DiagnosticCode::Log(_) | DiagnosticCode::Build => None,
                 };
 
                 filtered.push(lsp_types::Diagnostic {
@@ -176,6 +231,11 @@ pub fn collect_filtered(
                     severity: Some(diagnostic.severity),
                     message: diagnostic.message.clone(),
                     source: Some(source.to_string()),
+                    related_information: if diagnostic.related_information.is_empty() {
+                        None
+                    } else {
+                        Some(diagnostic.related_information.clone())
+                    },
                     ..Default::default()
                 });
             }
@@ -2,14 +2,34 @@ use lsp_types::DiagnosticSeverity;
 use rowan::{ast::AstNode, TextRange};
 
 use crate::{
-    db::document::Document,
+    db::{document::Document, workspace::Workspace},
+    options::BibtexDialect,
     syntax::bibtex::{self, HasDelims, HasEq, HasName, HasType, HasValue},
-    util::line_index_ext::LineIndexExt,
+    util::{
+        lang_data::{BibtexDataModel, BibtexEntryTypeFields, LANGUAGE_DATA},
+        line_index_ext::LineIndexExt,
+    },
     Db,
 };
 
 use super::{BibCode, Diagnostic, DiagnosticCode};
 
+/// Fields which reference managers and online databases like DBLP, Zotero or JabRef
+/// add to their exports although they are not part of any data model.
+const EXPORTER_FIELDS: &[&str] = &[
+    "abstract",
+    "bibsource",
+    "biburl",
+    "copyright",
+    "file",
+    "groups",
+    "keywords",
+    "mendeley-tags",
+    "owner",
+    "timestamp",
+    "urldate",
+];
+
 #[salsa::tracked(return_ref)]
 pub fn collect(db: &dyn Db, document: Document) -> Vec<Diagnostic> {
     let mut results = Vec::new();
@@ -42,6 +62,7 @@ fn analyze_entry(
             range: line_index.line_col_lsp_range(entry.type_token()?.text_range()),
             code: DiagnosticCode::Bib(code),
             message: String::from(code),
+            related_information: Vec::new(),
         });
 
         return Some(());
@@ -54,6 +75,7 @@ fn analyze_entry(
             range: line_index.line_col_lsp_range(entry.left_delim_token()?.text_range()),
             code: DiagnosticCode::Bib(code),
             message: String::from(code),
+            related_information: Vec::new(),
         });
 
         return Some(());
@@ -67,11 +89,74 @@ fn analyze_entry(
                 .line_col_lsp_range(TextRange::empty(entry.syntax().text_range().end())),
             code: DiagnosticCode::Bib(code),
             message: String::from(code),
+            related_information: Vec::new(),
         });
 
         return Some(());
     }
 
+    analyze_entry_type(db, document, &entry, results);
+    Some(())
+}
+
+fn analyze_entry_type(
+    db: &dyn Db,
+    document: Document,
+    entry: &bibtex::Entry,
+    results: &mut Vec<Diagnostic>,
+) -> Option<()> {
+    let line_index = document.contents(db).line_index(db);
+
+    let type_token = entry.type_token()?;
+    let fields = match find_entry_type(db, entry) {
+        Some(fields) => fields,
+        None => {
+            let code = BibCode::UnknownEntryType;
+            results.push(Diagnostic {
+                severity: DiagnosticSeverity::WARNING,
+                range: line_index.line_col_lsp_range(type_token.text_range()),
+                code: DiagnosticCode::Bib(code),
+                message: String::from(code),
+                related_information: Vec::new(),
+            });
+
+            return Some(());
+        }
+    };
+
+    let field_names: Vec<String> = entry
+        .fields()
+        .filter_map(|field| field.name_token())
+        .map(|name| name.text().to_lowercase())
+        .collect();
+
+    // The missing fields may be inherited from the referenced entries.
+    if field_names
+        .iter()
+        .any(|name| name == "crossref" || name == "xdata")
+    {
+        return Some(());
+    }
+
+    let range = entry.name_token().unwrap_or(type_token).text_range();
+    for required in &fields.required {
+        if required
+            .split('/')
+            .any(|alternative| field_names.iter().any(|name| name == alternative))
+        {
+            continue;
+        }
+
+        let code = BibCode::MissingRequiredField;
+        results.push(Diagnostic {
+            severity: DiagnosticSeverity::WARNING,
+            range: line_index.line_col_lsp_range(range),
+            code: DiagnosticCode::Bib(code),
+            message: format!("{}: {}", String::from(code), required.replace('/', " or ")),
+            related_information: Vec::new(),
+        });
+    }
+
     Some(())
 }
 
@@ -91,6 +176,7 @@ fn analyze_field(
             range: line_index.line_col_lsp_range(field.name_token()?.text_range()),
             code: DiagnosticCode::Bib(code),
             message: String::from(code),
+            related_information: Vec::new(),
         });
 
         return Some(());
@@ -103,10 +189,45 @@ fn analyze_field(
             range: line_index.line_col_lsp_range(field.name_token()?.text_range()),
             code: DiagnosticCode::Bib(code),
             message: String::from(code),
+            related_information: Vec::new(),
         });
 
         return Some(());
     }
 
+    if !Workspace::get(db).options(db).diagnostics.unknown_fields {
+        return Some(());
+    }
+
+    let name = field.name_token()?;
+    if EXPORTER_FIELDS.contains(&name.text().to_lowercase().as_str()) {
+        return Some(());
+    }
+
+    let entry = field.syntax().parent().and_then(bibtex::Entry::cast)?;
+    let fields = find_entry_type(db, &entry)?;
+    if !data_model(db).is_known_field(fields, name.text()) {
+        let code = BibCode::UnknownField;
+        results.push(Diagnostic {
+            severity: DiagnosticSeverity::WARNING,
+            range: line_index.line_col_lsp_range(name.text_range()),
+            code: DiagnosticCode::Bib(code),
+            message: String::from(code),
+            related_information: Vec::new(),
+        });
+    }
+
     Some(())
 }
+
+fn data_model(db: &dyn Db) -> &'static BibtexDataModel {
+    match Workspace::get(db).options(db).bibtex_dialect {
+        BibtexDialect::Bibtex => &LANGUAGE_DATA.data_models.bibtex,
+        BibtexDialect::Biblatex => &LANGUAGE_DATA.data_models.biblatex,
+    }
+}
+
+fn find_entry_type(db: &dyn Db, entry: &bibtex::Entry) -> Option<&'static BibtexEntryTypeFields> {
+    let type_token = entry.type_token()?;
+    data_model(db).find_entry_type(&type_token.text()[1..])
+}
@@ -0,0 +1,105 @@
+use lsp_types::DiagnosticSeverity;
+use rustc_hash::FxHashSet;
+
+use crate::{
+    db::{document::Document, parse::DocumentData, workspace::Workspace},
+    syntax::latex,
+    util::{
+        bibliography::{citation_keys, entry_keys},
+        line_index_ext::LineIndexExt,
+    },
+    Db,
+};
+
+use super::{BibCode, Diagnostic, DiagnosticCode, TexCode};
+
+#[salsa::tracked(return_ref)]
+pub fn collect(db: &dyn Db, workspace: Workspace, document: Document) -> Vec<Diagnostic> {
+    let mut results = Vec::new();
+    let line_index = document.contents(db).line_index(db);
+
+    match document.parse(db) {
+        DocumentData::Tex(data) => {
+            if !document.location(db).uri(db).as_str().ends_with(".tex") {
+                return results;
+            }
+
+            let has_bibliography = workspace
+                .related(db, document)
+                .iter()
+                .any(|document| document.parse(db).as_bib().is_some());
+
+            if !has_bibliography {
+                return results;
+            }
+
+            let entries = project_entry_keys(db, workspace, document);
+            for key in citation_keys(&data.root(db)).filter(|key| {
+                let text = key.to_string();
+                text != "*" && !entries.contains(&text)
+            }) {
+                let code = TexCode::UndefinedCitation;
+                results.push(Diagnostic {
+                    severity: DiagnosticSeverity::ERROR,
+                    range: line_index.line_col_lsp_range(latex::small_range(&key)),
+                    code: DiagnosticCode::Tex(code),
+                    message: String::from(code),
+                    related_information: Vec::new(),
+                });
+            }
+        }
+        DocumentData::Bib(data) => {
+            if !workspace.options(db).diagnostics.unused_entries {
+                return results;
+            }
+
+            let citations = project_citation_keys(db, workspace, document);
+            if citations.contains("*") {
+                return results;
+            }
+
+            for (_, key) in
+                entry_keys(&data.root(db)).filter(|(_, key)| !citations.contains(key.text()))
+            {
+                let code = BibCode::UnusedEntry;
+                results.push(Diagnostic {
+                    severity: DiagnosticSeverity::HINT,
+                    range: line_index.line_col_lsp_range(key.text_range()),
+                    code: DiagnosticCode::Bib(code),
+                    message: String::from(code),
+                    related_information: Vec::new(),
+                });
+            }
+        }
+        DocumentData::Log(_)
+        | DocumentData::TexlabRoot(_)
+        | DocumentData::Tectonic(_)
+        | DocumentData::Latexmkrc(_) => {}
+    };
+
+    results
+}
+
+fn project_entry_keys(db: &dyn Db, workspace: Workspace, document: Document) -> FxHashSet<String> {
+    workspace
+        .related(db, document)
+        .iter()
+        .filter_map(|document| document.parse(db).as_bib())
+        .flat_map(|data| entry_keys(&data.root(db)))
+        .map(|(_, key)| key.text().into())
+        .collect()
+}
+
+fn project_citation_keys(
+    db: &dyn Db,
+    workspace: Workspace,
+    document: Document,
+) -> FxHashSet<String> {
+    workspace
+        .related(db, document)
+        .iter()
+        .filter_map(|document| document.parse(db).as_tex())
+        .flat_map(|data| citation_keys(&data.root(db)))
+        .map(|key| key.to_string())
+        .collect()
+}
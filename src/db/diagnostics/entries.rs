@@ -0,0 +1,212 @@
+use lsp_types::{DiagnosticRelatedInformation, DiagnosticSeverity, Location};
+use rowan::{ast::AstNode, TextRange};
+use rustc_hash::FxHashMap;
+
+use crate::{
+    citation::field::text::TextFieldData,
+    db::{document::Document, workspace::Workspace},
+    syntax::bibtex::{self, HasName, HasValue},
+    util::line_index_ext::LineIndexExt,
+    Db,
+};
+
+use super::{BibCode, Diagnostic, DiagnosticCode};
+
+#[salsa::tracked(return_ref)]
+pub fn collect(db: &dyn Db, workspace: Workspace, document: Document) -> Vec<Diagnostic> {
+    let mut results = Vec::new();
+    if document.parse(db).as_bib().is_none() {
+        return results;
+    }
+
+    let entries = project_entries(db, workspace, document);
+    let mut entries_by_key: FxHashMap<&str, Vec<&EntryInfo>> = FxHashMap::default();
+    let mut entries_by_work: FxHashMap<&str, Vec<&EntryInfo>> = FxHashMap::default();
+    for entry in &entries {
+        entries_by_key
+            .entry(entry.normalized_key.as_str())
+            .or_default()
+            .push(entry);
+        for fingerprint in &entry.fingerprints {
+            entries_by_work
+                .entry(fingerprint.as_str())
+                .or_default()
+                .push(entry);
+        }
+    }
+
+    let line_index = document.contents(db).line_index(db);
+    let report_likely = workspace.options(db).diagnostics.likely_duplicate_entries;
+    for entry in entries.iter().filter(|entry| entry.document == document) {
+        let duplicates: Vec<_> = entries_by_key[entry.normalized_key.as_str()]
+            .iter()
+            .filter(|other| !other.is_same(entry))
+            .map(|other| other.related_information(db, "Other definition".into()))
+            .collect();
+
+        if !duplicates.is_empty() {
+            let code = BibCode::DuplicateEntry;
+            results.push(Diagnostic {
+                severity: DiagnosticSeverity::ERROR,
+                range: line_index.line_col_lsp_range(entry.range),
+                code: DiagnosticCode::Bib(code),
+                message: String::from(code),
+                related_information: duplicates,
+            });
+        }
+
+        if !report_likely {
+            continue;
+        }
+
+        let mut similar: Vec<&EntryInfo> = entry
+            .fingerprints
+            .iter()
+            .flat_map(|fingerprint| &entries_by_work[fingerprint.as_str()])
+            .copied()
+            .filter(|other| other.normalized_key != entry.normalized_key)
+            .collect();
+
+        similar.sort_by_key(|other| (other.document, other.range.start()));
+        similar.dedup_by_key(|other| (other.document, other.range.start()));
+        if !similar.is_empty() {
+            let code = BibCode::LikelyDuplicateEntry;
+            results.push(Diagnostic {
+                severity: DiagnosticSeverity::WARNING,
+                range: line_index.line_col_lsp_range(entry.range),
+                code: DiagnosticCode::Bib(code),
+                message: String::from(code),
+                related_information: similar
+                    .into_iter()
+                    .map(|other| {
+                        let message = format!("Entry \"{}\" describes the same work", other.key);
+                        other.related_information(db, message)
+                    })
+                    .collect(),
+            });
+        }
+    }
+
+    results
+}
+
+fn project_entries(db: &dyn Db, workspace: Workspace, document: Document) -> Vec<EntryInfo> {
+    let mut entries: Vec<EntryInfo> = workspace
+        .related(db, document)
+        .iter()
+        .filter_map(|&document| Some((document, document.parse(db).as_bib()?)))
+        .flat_map(|(document, data)| {
+            data.root(db)
+                .children()
+                .filter_map(bibtex::Entry::cast)
+                .filter_map(|entry| EntryInfo::new(document, &entry))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    entries.sort_by_key(|entry| (entry.document, entry.range.start()));
+    entries
+}
+
+#[derive(Debug)]
+struct EntryInfo {
+    document: Document,
+    key: String,
+    /// BibTeX and biber do not distinguish keys which only differ in case.
+    normalized_key: String,
+    range: TextRange,
+    fingerprints: Vec<String>,
+}
+
+impl EntryInfo {
+    fn new(document: Document, entry: &bibtex::Entry) -> Option<Self> {
+        let key = entry.name_token()?;
+
+        let mut doi = None;
+        let mut title = None;
+        let mut year = None;
+        for field in entry.fields() {
+            let (name, value) = match (field.name_token(), field.value()) {
+                (Some(name), Some(value)) => (name, value),
+                _ => continue,
+            };
+
+            let text = match TextFieldData::parse(&value) {
+                Some(data) => data.text,
+                None => continue,
+            };
+
+            match name.text().to_lowercase().as_str() {
+                "doi" => doi = normalize_doi(&text),
+                "title" => title = normalize_title(&text),
+                "year" | "date" => year = year.or_else(|| parse_year(&text)),
+                _ => {}
+            };
+        }
+
+        let mut fingerprints = Vec::new();
+        if let Some(doi) = doi {
+            fingerprints.push(format!("doi:{doi}"));
+        }
+
+        if let (Some(title), Some(year)) = (title, year) {
+            fingerprints.push(format!("title:{year}:{title}"));
+        }
+
+        Some(Self {
+            document,
+            key: key.text().into(),
+            normalized_key: key.text().to_lowercase(),
+            range: key.text_range(),
+            fingerprints,
+        })
+    }
+
+    fn is_same(&self, other: &Self) -> bool {
+        self.document == other.document && self.range == other.range
+    }
+
+    fn related_information(&self, db: &dyn Db, message: String) -> DiagnosticRelatedInformation {
+        let line_index = self.document.contents(db).line_index(db);
+        DiagnosticRelatedInformation {
+            location: Location::new(
+                self.document.location(db).uri(db).clone(),
+                line_index.line_col_lsp_range(self.range),
+            ),
+            message,
+        }
+    }
+}
+
+fn normalize_doi(text: &str) -> Option<String> {
+    let doi = text.trim().to_lowercase();
+    let doi = [
+        "https://doi.org/",
+        "http://doi.org/",
+        "https://dx.doi.org/",
+        "http://dx.doi.org/",
+        "doi:",
+    ]
+    .iter()
+    .find_map(|prefix| doi.strip_prefix(prefix))
+    .unwrap_or(&doi)
+    .trim();
+
+    (!doi.is_empty()).then(|| doi.to_string())
+}
+
+fn normalize_title(text: &str) -> Option<String> {
+    let words: Vec<_> = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect();
+
+    (!words.is_empty()).then(|| words.join(" "))
+}
+
+fn parse_year(text: &str) -> Option<String> {
+    text.split(|c: char| !c.is_ascii_digit())
+        .find(|part| part.len() == 4)
+        .map(String::from)
+}
@@ -0,0 +1,102 @@
+use lsp_types::{DiagnosticRelatedInformation, DiagnosticSeverity, Location};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    db::{analysis::label, document::Document, workspace::Workspace, Word},
+    util::line_index_ext::LineIndexExt,
+    Db,
+};
+
+use super::{Diagnostic, DiagnosticCode, TexCode};
+
+#[salsa::tracked(return_ref)]
+pub fn collect(db: &dyn Db, workspace: Workspace, document: Document) -> Vec<Diagnostic> {
+    let mut results = Vec::new();
+
+    if !document.location(db).uri(db).as_str().ends_with(".tex") {
+        return results;
+    }
+
+    let data = match document.parse(db).as_tex() {
+        Some(data) => data,
+        None => return results,
+    };
+
+    let mut definitions: FxHashMap<Word, Vec<(Document, label::Name)>> = FxHashMap::default();
+    let mut references: FxHashSet<Word> = FxHashSet::default();
+    for &other_document in workspace.related(db, document) {
+        let labels = match other_document.parse(db).as_tex() {
+            Some(data) => data.analyze(db).labels(db),
+            None => continue,
+        };
+
+        for label in labels.iter().copied() {
+            match label.origin(db) {
+                label::Origin::Definition(_) => {
+                    definitions
+                        .entry(label.name(db))
+                        .or_default()
+                        .push((other_document, label));
+                }
+                label::Origin::Reference(_) | label::Origin::ReferenceRange(_) => {
+                    references.insert(label.name(db));
+                }
+            };
+        }
+    }
+
+    let line_index = document.contents(db).line_index(db);
+    let report_unused = workspace.options(db).diagnostics.unused_labels;
+    for label in data.analyze(db).labels(db).iter().copied() {
+        let name = label.name(db);
+        let mut related_information = Vec::new();
+        let code = match label.origin(db) {
+            label::Origin::Definition(_) => {
+                related_information = definitions[&name]
+                    .iter()
+                    .filter(|(_, other)| *other != label)
+                    .map(|&(other_document, other)| DiagnosticRelatedInformation {
+                        location: Location::new(
+                            other_document.location(db).uri(db).clone(),
+                            other_document
+                                .contents(db)
+                                .line_index(db)
+                                .line_col_lsp_range(other.range(db)),
+                        ),
+                        message: "Other definition".into(),
+                    })
+                    .collect();
+
+                if !related_information.is_empty() {
+                    TexCode::DuplicateLabel
+                } else if report_unused && !references.contains(&name) {
+                    TexCode::UnusedLabel
+                } else {
+                    continue;
+                }
+            }
+            label::Origin::Reference(_) | label::Origin::ReferenceRange(_) => {
+                if definitions.contains_key(&name) {
+                    continue;
+                }
+
+                TexCode::UndefinedLabel
+            }
+        };
+
+        let severity = match code {
+            TexCode::UnusedLabel => DiagnosticSeverity::HINT,
+            _ => DiagnosticSeverity::ERROR,
+        };
+
+        results.push(Diagnostic {
+            severity,
+            range: line_index.line_col_lsp_range(label.range(db)),
+            code: DiagnosticCode::Tex(code),
+            message: String::from(code),
+            related_information,
+        });
+    }
+
+    results
+}
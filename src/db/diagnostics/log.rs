@@ -3,8 +3,12 @@ use rowan::{TextLen, TextRange, TextSize};
 use rustc_hash::FxHashMap;
 
 use crate::{
-    db::{document::Document, workspace::Workspace},
-    syntax::{BuildError, BuildErrorLevel},
+    db::{
+        analysis::label,
+        document::{Document, Language},
+        workspace::Workspace,
+    },
+    syntax::{BuildError, BuildErrorKind, BuildErrorLevel},
     util::line_index_ext::LineIndexExt,
     Db,
 };
@@ -29,61 +33,78 @@ pub fn collect(
         None => return results,
     };
 
-    let base_path = match root_document
-        .location(db)
-        .path(db)
-        .as_deref()
-        .and_then(|path| path.parent())
-    {
-        Some(path) => path,
-        None => return results,
+    let is_blg = log_document.language(db) == Language::Blg;
+    for error in &log.errors {
+        let code = DiagnosticCode::Log(log_document);
+        if let Some((document, diagnostic)) = resolve(db, workspace, root_document, error, code) {
+            // BibTeX and biber report lines of bibliography files, which are meaningless
+            // in the root document if the bibliography is not part of the workspace.
+            if is_blg && document == root_document {
+                continue;
+            }
+
+            results.entry(document).or_default().push(diagnostic);
+        }
+    }
+
+    results
+}
+
+/// Converts an error of a build of the given root document into a diagnostic
+/// and finds the document it belongs to.
+pub fn resolve(
+    db: &dyn Db,
+    workspace: Workspace,
+    root_document: Document,
+    error: &BuildError,
+    code: DiagnosticCode,
+) -> Option<(Document, Diagnostic)> {
+    let base_path = root_document.location(db).path(db).as_deref()?.parent()?;
+    let full_path = base_path.join(&error.relative_path);
+    let full_path_uri = Url::from_file_path(&full_path).ok()?;
+
+    let severity = match error.level {
+        BuildErrorLevel::Error => DiagnosticSeverity::ERROR,
+        BuildErrorLevel::Warning => DiagnosticSeverity::WARNING,
     };
 
-    for error in &log.errors {
-        let full_path = base_path.join(&error.relative_path);
-        let full_path_uri = if let Ok(uri) = Url::from_file_path(&full_path) {
-            uri
-        } else {
-            continue;
-        };
-
-        let severity = match error.level {
-            BuildErrorLevel::Error => DiagnosticSeverity::ERROR,
-            BuildErrorLevel::Warning => DiagnosticSeverity::WARNING,
-        };
-
-        let range = find_range_of_hint(db, workspace, &full_path_uri, error).unwrap_or_else(|| {
+    let tex_document = workspace.lookup_uri(db, &full_path_uri);
+    let range = tex_document
+        .and_then(|document| find_range(db, document, error))
+        .unwrap_or_else(|| {
             let line = error.line.unwrap_or(0);
             Range::new(Position::new(line, 0), Position::new(line, 0))
         });
 
-        let diagnostic = Diagnostic {
-            severity,
-            range,
-            code: DiagnosticCode::Log(log_document),
-            message: error.message.clone(),
-        };
+    let diagnostic = Diagnostic {
+        severity,
+        range,
+        code,
+        message: error.message.clone(),
+        related_information: Vec::new(),
+    };
 
-        let tex_document = workspace
-            .lookup_uri(db, &full_path_uri)
-            .unwrap_or(root_document);
+    Some((tex_document.unwrap_or(root_document), diagnostic))
+}
 
-        results.entry(tex_document).or_default().push(diagnostic);
+/// Finds the label or key named by the error on the reported line
+/// and falls back to the context TeX printed for it.
+fn find_range(db: &dyn Db, document: Document, error: &BuildError) -> Option<Range> {
+    let key = error.kind.key();
+    match error.line {
+        Some(line) => key
+            .and_then(|key| find_range_on_line(db, document, line, key))
+            .or_else(|| find_range_on_line(db, document, line, error.hint.as_deref()?)),
+        None => match &error.kind {
+            BuildErrorKind::MultiplyDefinedLabel(name) => find_label_definition(db, document, name),
+            _ => None,
+        },
     }
-
-    results
 }
 
-fn find_range_of_hint(
-    db: &dyn Db,
-    workspace: Workspace,
-    uri: &Url,
-    error: &BuildError,
-) -> Option<Range> {
-    let document = workspace.lookup_uri(db, uri)?;
+fn find_range_on_line(db: &dyn Db, document: Document, line: u32, needle: &str) -> Option<Range> {
     let text = document.contents(db).text(db);
-    let line = error.line? as usize;
-    let hint = error.hint.as_deref()?;
+    let line = line as usize;
     let line_index = document.contents(db).line_index(db);
 
     let line_start = line_index.newlines.get(line).copied()?;
@@ -94,7 +115,17 @@ fn find_range_of_hint(
         .unwrap_or(text.text_len());
 
     let line_text = &text[line_start.into()..line_end.into()];
-    let hint_start = line_start + TextSize::try_from(line_text.find(hint)?).unwrap();
-    let hint_end = hint_start + hint.text_len();
-    Some(line_index.line_col_lsp_range(TextRange::new(hint_start, hint_end)))
+    let start = line_start + TextSize::try_from(line_text.find(needle)?).unwrap();
+    let end = start + needle.text_len();
+    Some(line_index.line_col_lsp_range(TextRange::new(start, end)))
+}
+
+fn find_label_definition(db: &dyn Db, document: Document, name: &str) -> Option<Range> {
+    let data = document.parse(db).as_tex()?;
+    let label = data.analyze(db).labels(db).iter().find(|label| {
+        matches!(label.origin(db), label::Origin::Definition(_)) && label.name(db).text(db) == name
+    })?;
+
+    let line_index = document.contents(db).line_index(db);
+    Some(line_index.line_col_lsp_range(label.range(db)))
 }
@@ -46,6 +46,7 @@ fn analyze_environment(
                 .line_col_lsp_range(latex::small_range(&name1)),
             code: DiagnosticCode::Tex(code),
             message: String::from(code),
+            related_information: Vec::new(),
         });
     }
 
@@ -94,6 +95,7 @@ fn analyze_curly_group(
                 .line_col_lsp_range(TextRange::empty(node.text_range().end())),
             code: DiagnosticCode::Tex(code),
             message: String::from(code),
+            related_information: Vec::new(),
         });
     }
 
@@ -116,6 +118,7 @@ fn analyze_curly_braces(
                 .line_col_lsp_range(node.text_range()),
             code: DiagnosticCode::Tex(code),
             message: String::from(code),
+            related_information: Vec::new(),
         });
 
         Some(())
@@ -1,10 +1,13 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use itertools::Itertools;
 use lsp_types::Url;
 use rustc_hash::FxHashSet;
 
-use crate::{util::HOME_DIR, Db};
+use crate::{
+    util::{magic_comment::find_magic_comment, HOME_DIR},
+    Db,
+};
 
 use super::{
     analysis::TexLink,
@@ -42,6 +45,7 @@ pub fn hidden_dependencies(
 ) {
     dependencies.extend(hidden_dependency(db, document, base_dir, "log"));
     dependencies.extend(hidden_dependency(db, document, base_dir, "aux"));
+    dependencies.extend(hidden_dependency(db, document, base_dir, "blg"));
 }
 
 #[salsa::tracked]
@@ -56,7 +60,7 @@ pub fn hidden_dependency(
     let stem = source.location(db).stem(db)?;
     let name = format!("{stem}.{extension}");
 
-    let location = workspace.output_dir(db, base_dir).join(db, &name)?;
+    let location = workspace.aux_dir(db, base_dir).join(db, &name)?;
     match workspace.lookup(db, location) {
         Some(target) => Some(Dependency {
             source,
@@ -144,6 +148,25 @@ pub fn source_dependency(
     None
 }
 
+/// Finds the root document named by a `% !TEX root = ...` magic comment,
+/// which takes precedence over the parents found in the dependency graph.
+#[salsa::tracked]
+pub fn magic_root(db: &dyn Db, document: Document) -> Option<Document> {
+    if document.language(db) != Language::Tex {
+        return None;
+    }
+
+    let path = find_magic_comment(document.contents(db).text(db), "root")?;
+    let location = document.directory(db).join(db, path)?;
+    match Workspace::get(db).lookup(db, location) {
+        Some(root) => Some(root).filter(|&root| root != document),
+        None => {
+            MissingDependencies::push(db, MissingDependency { location });
+            None
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct DependencyGraph {
     pub start: Document,
@@ -162,6 +185,9 @@ impl DependencyGraph {
 pub fn dependency_graph(db: &dyn Db, start: Document) -> DependencyGraph {
     let workspace = Workspace::get(db);
 
+    // Resolving the magic comment makes sure that the root document gets loaded.
+    magic_root(db, start);
+
     let base_dir = workspace.working_dir(db, start.directory(db));
     let mut edges = Vec::new();
     let mut stack = vec![(start, base_dir)];
@@ -230,7 +256,7 @@ fn discover_parents(db: &mut dyn Db, workspace: Workspace) -> bool {
         if let Some(language) = Language::from_path(&path) {
             let can_be_parent = matches!(
                 language,
-                Language::Tex | Language::TexlabRoot | Language::Tectonic
+                Language::Tex | Language::TexlabRoot | Language::Tectonic | Language::Latexmkrc
             );
 
             if can_be_parent && workspace.lookup_path(db, &path).is_none() {
@@ -239,9 +265,31 @@ fn discover_parents(db: &mut dyn Db, workspace: Workspace) -> bool {
         }
     }
 
+    for path in user_latexmkrc_paths() {
+        if path.is_file() && workspace.lookup_path(db, &path).is_none() {
+            changed |= workspace
+                .load(db, &path, Language::Latexmkrc, Owner::Server)
+                .is_some();
+        }
+    }
+
     changed
 }
 
+/// The `latexmkrc` files of the user, which apply to every project.
+pub fn user_latexmkrc_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if let Some(dir) = dirs::config_dir() {
+        paths.push(dir.join("latexmk").join("latexmkrc"));
+    }
+
+    if let Some(dir) = HOME_DIR.as_deref() {
+        paths.push(dir.join(".latexmkrc"));
+    }
+
+    paths
+}
+
 fn is_part_of_workspace(db: &dyn Db, workspace: Workspace, path: &Path) -> bool {
     let root_dirs = workspace.root_dirs(db);
     if root_dirs.is_empty() {
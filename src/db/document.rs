@@ -6,9 +6,12 @@ use rowan::{TextRange, TextSize};
 use crate::{
     db::{
         diagnostics::Diagnostic,
-        parse::{BibDocumentData, LogDocumentData, TectonicData, TexDocumentData, TexlabRootData},
+        parse::{
+            BibDocumentData, LatexmkrcData, LogDocumentData, TectonicData, TexDocumentData,
+            TexlabRootData,
+        },
     },
-    parser::{parse_bibtex, parse_build_log, parse_latex},
+    parser::{parse_bibtex, parse_blg, parse_build_log, parse_latex, parse_latexmkrc},
     util::line_index::LineIndex,
     Db,
 };
@@ -73,8 +76,10 @@ pub enum Language {
     Tex,
     Bib,
     Log,
+    Blg,
     TexlabRoot,
     Tectonic,
+    Latexmkrc,
 }
 
 impl Language {
@@ -88,11 +93,16 @@ impl Language {
             return Some(Self::Tectonic);
         }
 
+        if name.eq_ignore_ascii_case(".latexmkrc") || name.eq_ignore_ascii_case("latexmkrc") {
+            return Some(Self::Latexmkrc);
+        }
+
         let extname = path.extension()?.to_str()?;
         match extname.to_lowercase().as_str() {
             "tex" | "sty" | "cls" | "def" | "lco" | "aux" | "rnw" => Some(Self::Tex),
             "bib" | "bibtex" => Some(Self::Bib),
             "log" => Some(Self::Log),
+            "blg" => Some(Self::Blg),
             _ => None,
         }
     }
@@ -111,6 +121,9 @@ impl Language {
 pub struct LinterData {
     #[return_ref]
     pub chktex: Vec<Diagnostic>,
+
+    #[return_ref]
+    pub build: Vec<Diagnostic>,
 }
 
 #[salsa::input]
@@ -163,6 +176,10 @@ impl Document {
                 let data = LogDocumentData::new(db, parse_build_log(text));
                 DocumentData::Log(data)
             }
+            Language::Blg => {
+                let data = LogDocumentData::new(db, parse_blg(text));
+                DocumentData::Log(data)
+            }
             Language::TexlabRoot => {
                 let data = TexlabRootData;
                 DocumentData::TexlabRoot(data)
@@ -171,6 +188,10 @@ impl Document {
                 let data = TectonicData;
                 DocumentData::Tectonic(data)
             }
+            Language::Latexmkrc => {
+                let data = LatexmkrcData::new(db, parse_latexmkrc(text));
+                DocumentData::Latexmkrc(data)
+            }
         }
     }
 
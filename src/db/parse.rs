@@ -1,6 +1,6 @@
 use crate::{
     db::analysis::TexAnalysis,
-    syntax::{bibtex, latex, BuildLog},
+    syntax::{bibtex, latex, BuildLog, Latexmkrc},
     Db,
 };
 
@@ -40,6 +40,11 @@ pub struct LogDocumentData {
     pub log: BuildLog,
 }
 
+#[salsa::interned]
+pub struct LatexmkrcData {
+    pub config: Latexmkrc,
+}
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 pub struct TexlabRootData;
 
@@ -53,6 +58,7 @@ pub enum DocumentData {
     Log(LogDocumentData),
     TexlabRoot(TexlabRootData),
     Tectonic(TectonicData),
+    Latexmkrc(LatexmkrcData),
 }
 
 impl DocumentData {
@@ -76,4 +82,11 @@ impl DocumentData {
             _ => None,
         }
     }
+
+    pub fn as_latexmkrc(self) -> Option<LatexmkrcData> {
+        match self {
+            Self::Latexmkrc(data) => Some(data),
+            _ => None,
+        }
+    }
 }
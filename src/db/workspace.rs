@@ -11,13 +11,14 @@ use rustc_hash::FxHashSet;
 use crate::{
     db::document::{Document, Location},
     distro::FileNameDB,
+    syntax::Latexmkrc,
     Db, Options,
 };
 
 use super::{
     dependency_graph,
     document::{Contents, Language, LinterData, Owner},
-    Word,
+    magic_root, user_latexmkrc_paths, Word,
 };
 
 #[salsa::input(singleton)]
@@ -97,7 +98,7 @@ impl Workspace {
                     language,
                     owner,
                     cursor,
-                    LinterData::new(db, Vec::new()),
+                    LinterData::new(db, Vec::new(), Vec::new()),
                 );
 
                 let mut documents = self.set_documents(db).to(FxHashSet::default());
@@ -137,7 +138,7 @@ impl Workspace {
             .documents(db)
             .iter()
             .map(|document| self.working_dir(db, document.directory(db)))
-            .map(|base_dir| self.output_dir(db, base_dir))
+            .flat_map(|base_dir| [self.output_dir(db, base_dir), self.aux_dir(db, base_dir)])
             .filter_map(|location| location.path(db).as_deref());
 
         self.documents(db)
@@ -182,23 +183,73 @@ impl Workspace {
 
     #[salsa::tracked]
     pub fn output_dir(self, db: &dyn Db, base_dir: Location) -> Location {
-        let mut path = self
+        let path = self
             .options(db)
             .aux_directory
             .as_deref()
             .and_then(|path| path.to_str())
-            .unwrap_or(".")
-            .to_string();
+            .map(ToOwned::to_owned)
+            .or_else(|| self.latexmkrc(db, base_dir).out_dir)
+            .unwrap_or_else(|| ".".to_string());
 
-        if !path.ends_with("/") {
-            path.push('/');
+        join_dir(db, base_dir, path)
+    }
+
+    /// The directory of the auxiliary files like `.aux` and `.log`.
+    /// It only differs from the output directory if `latexmkrc` sets `$aux_dir`.
+    #[salsa::tracked]
+    pub fn aux_dir(self, db: &dyn Db, base_dir: Location) -> Location {
+        if self.options(db).aux_directory.is_some() {
+            return self.output_dir(db, base_dir);
         }
 
-        base_dir.join(db, &path).unwrap_or(base_dir)
+        match self.latexmkrc(db, base_dir).aux_dir {
+            Some(path) => join_dir(db, base_dir, path),
+            None => self.output_dir(db, base_dir),
+        }
+    }
+
+    /// Merges the `latexmkrc` files which apply to the given directory.
+    /// The settings of the project take precedence over the settings of the user.
+    #[salsa::tracked]
+    pub fn latexmkrc(self, db: &dyn Db, base_dir: Location) -> Latexmkrc {
+        let user_paths = user_latexmkrc_paths();
+        let mut files: Vec<_> = self
+            .documents(db)
+            .iter()
+            .filter(|document| document.language(db) == Language::Latexmkrc)
+            .filter_map(|document| {
+                let config = document.parse(db).as_latexmkrc()?.config(db);
+                let path = document.location(db).path(db).as_deref()?;
+                let dir = document.directory(db).uri(db).as_str();
+                let priority = if user_paths.iter().any(|user_path| user_path == path) {
+                    0
+                } else if base_dir.uri(db).as_str().starts_with(dir) {
+                    dir.len()
+                } else {
+                    return None;
+                };
+
+                Some((priority, config))
+            })
+            .collect();
+
+        files.sort_by_key(|(priority, _)| *priority);
+        files
+            .into_iter()
+            .fold(Latexmkrc::default(), |result, (_, config)| Latexmkrc {
+                out_dir: config.out_dir.or(result.out_dir),
+                aux_dir: config.aux_dir.or(result.aux_dir),
+                pdf_mode: config.pdf_mode.or(result.pdf_mode),
+            })
     }
 
     #[salsa::tracked(return_ref)]
     pub fn parents(self, db: &dyn Db, child: Document) -> Vec<Document> {
+        if let Some(root) = magic_root(db, child) {
+            return vec![root];
+        }
+
         self.index_files(db)
             .filter(|&parent| dependency_graph(db, parent).preorder().contains(&child))
             .collect()
@@ -206,10 +257,13 @@ impl Workspace {
 
     #[salsa::tracked(return_ref)]
     pub fn related(self, db: &dyn Db, child: Document) -> FxHashSet<Document> {
+        let root = magic_root(db, child);
         self.index_files(db)
             .chain(self.documents(db).iter().copied())
             .map(|start| dependency_graph(db, start).preorder().collect_vec())
-            .filter(|project| project.contains(&child))
+            .filter(|project| {
+                project.contains(&child) || root.map_or(false, |root| project.contains(&root))
+            })
             .flatten()
             .collect()
     }
@@ -224,3 +278,83 @@ impl Workspace {
             .map(|number| number.text(db))
     }
 }
+
+fn join_dir(db: &dyn Db, base_dir: Location, mut path: String) -> Location {
+    if !path.ends_with('/') {
+        path.push('/');
+    }
+
+    base_dir.join(db, &path).unwrap_or(base_dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Database;
+
+    use super::*;
+
+    fn open(db: &mut Database, name: &str, text: &str) -> Document {
+        let uri = Url::parse("file:///texlab/").unwrap().join(name).unwrap();
+        let workspace = Workspace::get(&*db);
+        workspace.open(db, uri, text.to_string(), Language::Tex, Owner::Client)
+    }
+
+    #[test]
+    fn test_parents_magic_root() {
+        let mut db = Database::default();
+        let main = open(&mut db, "main.tex", r#"\documentclass{article}"#);
+        let chapter = open(&mut db, "chapter.tex", "% !TEX root = main.tex");
+
+        assert_eq!(Workspace::get(&db).parents(&db, chapter), vec![main]);
+    }
+
+    #[test]
+    fn test_parents_magic_root_missing() {
+        let mut db = Database::default();
+        let main = open(
+            &mut db,
+            "main.tex",
+            r#"\documentclass{article}\input{chapter}"#,
+        );
+        let chapter = open(&mut db, "chapter.tex", "% !TEX root = missing.tex");
+
+        assert_eq!(Workspace::get(&db).parents(&db, chapter), vec![main]);
+    }
+
+    #[test]
+    fn test_parents_magic_root_self() {
+        let mut db = Database::default();
+        let main = open(
+            &mut db,
+            "main.tex",
+            "% !TEX root = main.tex\n\\documentclass{article}",
+        );
+
+        assert_eq!(Workspace::get(&db).parents(&db, main), vec![main]);
+        assert!(Workspace::get(&db).related(&db, main).contains(&main));
+    }
+
+    #[test]
+    fn test_related_magic_root() {
+        let mut db = Database::default();
+        let main = open(&mut db, "main.tex", r#"\documentclass{article}"#);
+        let chapter = open(
+            &mut db,
+            "chapter.tex",
+            "% !TEX root = main.tex\n\\input{section}",
+        );
+        let section = open(&mut db, "section.tex", "");
+        let other = open(
+            &mut db,
+            "other.tex",
+            r#"\documentclass{article}\input{chapter}"#,
+        );
+        let unrelated = open(&mut db, "unrelated.tex", r#"\documentclass{article}"#);
+
+        let related = Workspace::get(&db).related(&db, chapter);
+        assert!(related.contains(&main));
+        assert!(related.contains(&section));
+        assert!(related.contains(&other));
+        assert!(!related.contains(&unrelated));
+    }
+}
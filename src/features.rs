@@ -1,4 +1,6 @@
 pub mod build;
+pub mod code_action;
+pub mod color;
 pub mod completion;
 pub mod definition;
 pub mod folding;
@@ -8,7 +10,12 @@ pub mod highlight;
 pub mod hover;
 pub mod inlay_hint;
 pub mod link;
+pub mod linked_editing;
 pub mod reference;
 pub mod rename;
+pub mod selection_range;
+pub mod semantic_tokens;
+pub mod signature_help;
 pub mod symbol;
+pub mod synctex;
 pub mod workspace_command;
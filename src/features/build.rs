@@ -1,9 +1,11 @@
+mod manager;
 mod progress;
 
 use std::{
     io::{BufRead, BufReader, Read},
     path::{Path, PathBuf},
     process::Stdio,
+    sync::Arc,
     thread::{self, JoinHandle},
 };
 
@@ -12,18 +14,48 @@ use lsp_types::{notification::LogMessage, LogMessageParams, TextDocumentIdentifi
 use serde::{Deserialize, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
-use crate::{client::LspClient, db::Workspace, util::capabilities::ClientCapabilitiesExt, Db};
+pub use self::manager::{BuildHandle, BuildManager};
+
+use crate::{
+    client::LspClient,
+    db::{Document, Workspace},
+    options::{BuildArgs, BuildOptions, BuildRecipe, BuildStep},
+    parser::BuildLogStream,
+    syntax::{BuildError, Latexmkrc},
+    util::{capabilities::ClientCapabilitiesExt, magic_comment::find_magic_comment},
+    Db,
+};
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BuildParams {
     pub text_document: TextDocumentIdentifier,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipe: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BuildResult {
     pub status: BuildStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failed_step: Option<FailedBuildStep>,
+}
+
+impl From<BuildStatus> for BuildResult {
+    fn from(status: BuildStatus) -> Self {
+        Self {
+            status,
+            failed_step: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailedBuildStep {
+    pub index: usize,
+    pub executable: String,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize_repr, Deserialize_repr)]
@@ -35,28 +67,37 @@ pub enum BuildStatus {
     CANCELLED = 3,
 }
 
+/// Finds the document which has to be compiled in order to build the given document.
+pub fn find_root(db: &dyn Db, uri: &Url) -> Option<Document> {
+    let workspace = Workspace::get(db);
+    let child = workspace.lookup_uri(db, uri)?;
+    let root = workspace
+        .parents(db, child)
+        .iter()
+        .next()
+        .copied()
+        .unwrap_or(child);
+
+    Some(root)
+}
+
 #[derive(Debug)]
 pub struct Command {
     uri: Url,
     progress: bool,
-    executable: String,
-    args: Vec<String>,
+    steps: Vec<BuildStep>,
     working_dir: PathBuf,
     client: LspClient,
 }
 
 impl Command {
-    pub fn new(db: &dyn Db, uri: Url, client: LspClient) -> Option<Self> {
+    pub fn uri(&self) -> &Url {
+        &self.uri
+    }
+
+    pub fn new(db: &dyn Db, uri: Url, recipe: Option<&str>, client: LspClient) -> Option<Self> {
         let workspace = Workspace::get(db);
-        let document = match workspace.lookup_uri(db, &uri) {
-            Some(child) => workspace
-                .parents(db, child)
-                .iter()
-                .next()
-                .copied()
-                .unwrap_or(child),
-            None => return None,
-        };
+        let document = find_root(db, &uri)?;
 
         if document.location(db).path(db).is_none() {
             log::warn!("Document {uri} cannot be compiled; skipping...");
@@ -64,33 +105,45 @@ impl Command {
         }
 
         let options = &workspace.options(db).build;
-        let executable = options.executable.0.clone();
+        let recipe = find_recipe(options, recipe, document.contents(db).text(db));
+
+        let base_dir = workspace.working_dir(db, document.directory(db));
+        let latexmkrc = workspace.latexmkrc(db, base_dir);
+
         let path = document.location(db).path(db).as_deref().unwrap();
-        let args = options
-            .args
-            .0
-            .iter()
-            .map(|arg| replace_placeholder(arg, path))
+        let steps = find_steps(options, recipe, &latexmkrc)
+            .into_iter()
+            .map(|step| BuildStep {
+                executable: step.executable,
+                args: step
+                    .args
+                    .iter()
+                    .map(|arg| replace_placeholder(arg, path))
+                    .collect(),
+            })
             .collect();
 
-        let working_dir = workspace
-            .working_dir(db, document.directory(db))
-            .path(db)
-            .clone()?;
+        let working_dir = base_dir.path(db).clone()?;
 
         Some(Self {
             uri: document.location(db).uri(db).clone(),
             progress: workspace
                 .client_capabilities(db)
                 .has_work_done_progress_support(),
-            executable,
-            args,
+            steps,
             working_dir,
             client,
         })
     }
 
-    pub fn run(self) -> BuildStatus {
+    /// Runs the steps in sequence and stops at the first step that does not succeed.
+    /// The errors found in the output are reported while the build is still running.
+    pub fn run(
+        self,
+        handle: &BuildHandle,
+        on_error: impl Fn(BuildError) + Send + Sync + 'static,
+    ) -> BuildResult {
+        let on_error: Arc<dyn Fn(BuildError) + Send + Sync> = Arc::new(on_error);
         let reporter = if self.progress {
             let inner = progress::Reporter::new(&self.client);
             inner.start(&self.uri).expect("report progress");
@@ -99,19 +152,61 @@ impl Command {
             None
         };
 
-        let mut process = match std::process::Command::new(&self.executable)
-            .args(self.args)
+        let mut result = BuildResult::from(BuildStatus::SUCCESS);
+        for (index, step) in self.steps.iter().enumerate() {
+            let status = if handle.is_cancelled() {
+                BuildStatus::CANCELLED
+            } else {
+                self.run_step(step, handle, Arc::clone(&on_error))
+            };
+
+            if status == BuildStatus::CANCELLED {
+                result = BuildResult::from(status);
+                break;
+            } else if status != BuildStatus::SUCCESS {
+                result = BuildResult {
+                    status,
+                    failed_step: Some(FailedBuildStep {
+                        index,
+                        executable: step.executable.clone(),
+                    }),
+                };
+
+                break;
+            }
+        }
+
+        drop(reporter);
+        result
+    }
+
+    fn run_step(
+        &self,
+        step: &BuildStep,
+        handle: &BuildHandle,
+        on_error: Arc<dyn Fn(BuildError) + Send + Sync>,
+    ) -> BuildStatus {
+        let mut command = std::process::Command::new(&step.executable);
+        command
+            .args(&step.args)
             .stdin(Stdio::null())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .current_dir(&self.working_dir)
-            .spawn()
+            .current_dir(&self.working_dir);
+
+        // Run every step in a new process group so that cancelling a build also stops its children.
+        #[cfg(unix)]
         {
+            use std::os::unix::process::CommandExt;
+            command.process_group(0);
+        }
+
+        let mut process = match command.spawn() {
             Ok(process) => process,
             Err(why) => {
                 log::error!(
                     "Failed to spawn process {:?} in directory {}: {}",
-                    self.executable,
+                    step.executable,
                     self.working_dir.display(),
                     why
                 );
@@ -119,18 +214,25 @@ impl Command {
             }
         };
 
+        handle.attach(process.id());
+
         let (line_sender, line_receiver) = flume::unbounded();
         let (exit_sender, exit_receiver) = flume::unbounded();
         track_output(process.stderr.take().unwrap(), line_sender.clone());
         track_output(process.stdout.take().unwrap(), line_sender);
         let client = self.client.clone();
-        let handle = std::thread::spawn(move || {
+        let logger = std::thread::spawn(move || {
             let typ = lsp_types::MessageType::LOG;
+            let mut log = BuildLogStream::default();
 
             loop {
                 let done = flume::Selector::new()
                     .recv(&line_receiver, |line| match line {
                         Ok(message) => {
+                            if let Some(error) = log.push_line(&message) {
+                                on_error(error);
+                            }
+
                             let params = LogMessageParams { message, typ };
                             let _ = client.send_notification::<LogMessage>(params);
                             false
@@ -147,21 +249,66 @@ impl Command {
         });
 
         let status = process.wait().map_or(BuildStatus::FAILURE, |result| {
-            if result.success() {
+            if handle.is_cancelled() {
+                BuildStatus::CANCELLED
+            } else if result.success() {
                 BuildStatus::SUCCESS
             } else {
                 BuildStatus::ERROR
             }
         });
 
+        handle.detach();
         let _ = exit_sender.send(());
-        handle.join().unwrap();
-
-        drop(reporter);
+        logger.join().unwrap();
         status
     }
 }
 
+/// Selects the recipe of a build. The recipe of the request wins over the magic comment
+/// of the document and the default recipe. A magic comment like `% !TEX program = xelatex`
+/// usually names a TeX engine, so it is only honoured if there is a recipe with that name.
+fn find_recipe<'a>(
+    options: &'a BuildOptions,
+    requested: Option<&str>,
+    text: &str,
+) -> Option<&'a BuildRecipe> {
+    let find = |name: &str| options.recipes.iter().find(|recipe| recipe.name == name);
+    let name = requested
+        .or_else(|| find_magic_comment(text, "program").filter(|&name| find(name).is_some()))
+        .or(options.default_recipe.as_deref())?;
+
+    let recipe = find(name);
+    if recipe.is_none() {
+        log::warn!("Unknown build recipe {name:?}; falling back to texlab.build.executable");
+    }
+
+    recipe
+}
+
+/// Resolves the steps of a build. The `executable` and `args` options are used
+/// if no recipe has been selected.
+fn find_steps(
+    options: &BuildOptions,
+    recipe: Option<&BuildRecipe>,
+    latexmkrc: &Latexmkrc,
+) -> Vec<BuildStep> {
+    if let Some(recipe) = recipe {
+        return recipe.steps.clone();
+    }
+
+    // The `-pdf` flag of the default arguments would override the engine selected by `$pdf_mode`.
+    let mut args = options.args.0.clone();
+    if options.args == BuildArgs::default() && latexmkrc.pdf_mode.is_some() {
+        args.retain(|arg| arg != "-pdf");
+    }
+
+    vec![BuildStep {
+        executable: options.executable.0.clone(),
+        args,
+    }]
+}
+
 fn track_output(
     output: impl Read + Send + 'static,
     sender: flume::Sender<String>,
@@ -189,3 +336,175 @@ fn replace_placeholder(arg: &str, file: &Path) -> String {
         arg.replace("%f", &file.to_string_lossy())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use lsp_types::Url;
+
+    use crate::{
+        client::LspClient,
+        options::{BuildArgs, BuildExecutable, BuildOptions, BuildRecipe, BuildStep},
+        syntax::Latexmkrc,
+    };
+
+    use super::{
+        find_recipe, find_steps, BuildHandle, BuildResult, BuildStatus, Command, FailedBuildStep,
+    };
+
+    fn step(executable: &str, args: &[&str]) -> BuildStep {
+        BuildStep {
+            executable: executable.to_string(),
+            args: args.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    fn options() -> BuildOptions {
+        BuildOptions {
+            recipes: vec![BuildRecipe {
+                name: "xelatex-biber".to_string(),
+                steps: vec![
+                    step("xelatex", &["%f"]),
+                    step("biber", &["%f"]),
+                    step("xelatex", &["%f"]),
+                ],
+            }],
+            ..BuildOptions::default()
+        }
+    }
+
+    fn command(steps: Vec<BuildStep>, working_dir: &Path) -> Command {
+        let (sender, _) = crossbeam_channel::unbounded();
+        Command {
+            uri: Url::parse("file:///texlab/main.tex").unwrap(),
+            progress: false,
+            steps,
+            working_dir: working_dir.to_path_buf(),
+            client: LspClient::new(sender),
+        }
+    }
+
+    #[test]
+    fn test_recipe_requested() {
+        let options = options();
+        let recipe = find_recipe(&options, Some("xelatex-biber"), "");
+        assert_eq!(recipe, Some(&options.recipes[0]));
+    }
+
+    #[test]
+    fn test_recipe_magic_comment() {
+        let options = options();
+        let text = "% !TEX program = xelatex-biber\n";
+        assert_eq!(find_recipe(&options, None, text), Some(&options.recipes[0]));
+    }
+
+    #[test]
+    fn test_recipe_magic_comment_engine() {
+        let options = options();
+        let text = "% !TEX program = xelatex\n";
+        assert_eq!(find_recipe(&options, None, text), None);
+    }
+
+    #[test]
+    fn test_recipe_default() {
+        let options = BuildOptions {
+            default_recipe: Some("xelatex-biber".to_string()),
+            ..options()
+        };
+
+        let text = "% !TEX program = xelatex\n";
+        assert_eq!(find_recipe(&options, None, text), Some(&options.recipes[0]));
+    }
+
+    #[test]
+    fn test_recipe_unknown() {
+        let options = options();
+        assert_eq!(find_recipe(&options, Some("lualatex"), ""), None);
+    }
+
+    #[test]
+    fn test_steps_recipe() {
+        let options = options();
+        let steps = find_steps(&options, options.recipes.first(), &Latexmkrc::default());
+        assert_eq!(steps, options.recipes[0].steps);
+    }
+
+    #[test]
+    fn test_steps_executable() {
+        let options = BuildOptions {
+            executable: BuildExecutable("tectonic".to_string()),
+            args: BuildArgs(vec!["%f".to_string()]),
+            ..options()
+        };
+
+        let steps = find_steps(&options, None, &Latexmkrc::default());
+        assert_eq!(steps, vec![step("tectonic", &["%f"])]);
+    }
+
+    #[test]
+    fn test_steps_latexmkrc_pdf_mode() {
+        let latexmkrc = Latexmkrc {
+            pdf_mode: Some(5),
+            ..Latexmkrc::default()
+        };
+
+        let steps = find_steps(&options(), None, &latexmkrc);
+        assert!(!steps[0].args.contains(&"-pdf".to_string()));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_run_stop_at_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let steps = vec![
+            step("true", &[]),
+            step("false", &[]),
+            step("touch", &["third"]),
+        ];
+
+        let result = command(steps, dir.path()).run(&BuildHandle::default(), |_| {});
+        assert_eq!(
+            result,
+            BuildResult {
+                status: BuildStatus::ERROR,
+                failed_step: Some(FailedBuildStep {
+                    index: 1,
+                    executable: "false".to_string(),
+                }),
+            }
+        );
+
+        assert!(!dir.path().join("third").exists());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_run_cancelled() {
+        use std::{sync::Arc, time::Duration};
+
+        let dir = tempfile::tempdir().unwrap();
+        let steps = vec![step("sleep", &["10"]), step("touch", &["second"])];
+        let handle = Arc::new(BuildHandle::default());
+        let canceller = {
+            let handle = Arc::clone(&handle);
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(200));
+                handle.cancel();
+            })
+        };
+
+        let result = command(steps, dir.path()).run(&handle, |_| {});
+        canceller.join().unwrap();
+        assert_eq!(result, BuildResult::from(BuildStatus::CANCELLED));
+        assert!(!dir.path().join("second").exists());
+    }
+
+    #[test]
+    fn test_run_missing_executable() {
+        let dir = tempfile::tempdir().unwrap();
+        let steps = vec![step("texlab-missing-executable", &[])];
+        let result = command(steps, dir.path()).run(&BuildHandle::default(), |_| {});
+        assert_eq!(result.status, BuildStatus::FAILURE);
+    }
+}
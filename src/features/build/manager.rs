@@ -0,0 +1,226 @@
+use std::{
+    process::Stdio,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use lsp_server::RequestId;
+use lsp_types::Url;
+use rustc_hash::FxHashMap;
+
+/// Keeps track of the running builds so that there is at most one build per root document.
+#[derive(Debug, Default, Clone)]
+pub struct BuildManager {
+    builds: Arc<Mutex<FxHashMap<Url, Arc<BuildHandle>>>>,
+    locks: Arc<Mutex<FxHashMap<Url, Arc<Mutex<()>>>>>,
+}
+
+impl BuildManager {
+    /// Registers a new build of the given root document and cancels the previous one.
+    /// The returned lock has to be held while the build is running.
+    pub fn start(
+        &self,
+        root: &Url,
+        request_id: Option<RequestId>,
+    ) -> (Arc<BuildHandle>, Arc<Mutex<()>>) {
+        let handle = Arc::new(BuildHandle {
+            request_id,
+            ..BuildHandle::default()
+        });
+
+        if let Some(previous) = self
+            .builds
+            .lock()
+            .unwrap()
+            .insert(root.clone(), Arc::clone(&handle))
+        {
+            previous.cancel();
+        }
+
+        let lock = Arc::clone(self.locks.lock().unwrap().entry(root.clone()).or_default());
+        (handle, lock)
+    }
+
+    /// Removes the build from the list of running builds unless it has been replaced already.
+    /// The lock of the root document is released as well if no other build is waiting for it.
+    pub fn finish(&self, root: &Url, handle: &Arc<BuildHandle>, lock: Arc<Mutex<()>>) {
+        let mut builds = self.builds.lock().unwrap();
+        if builds
+            .get(root)
+            .map_or(false, |current| Arc::ptr_eq(current, handle))
+        {
+            builds.remove(root);
+        }
+
+        let mut locks = self.locks.lock().unwrap();
+        drop(lock);
+        if locks
+            .get(root)
+            .map_or(false, |lock| Arc::strong_count(lock) == 1)
+        {
+            locks.remove(root);
+        }
+    }
+
+    /// Cancels the build of the given root document or all builds if no document is given.
+    pub fn cancel(&self, root: Option<&Url>) {
+        for (uri, handle) in self.builds.lock().unwrap().iter() {
+            if root.map_or(true, |root| root == uri) {
+                handle.cancel();
+            }
+        }
+    }
+
+    /// Cancels the build which has been started by the given request.
+    pub fn cancel_request(&self, request_id: &RequestId) {
+        for handle in self.builds.lock().unwrap().values() {
+            if handle.request_id.as_ref() == Some(request_id) {
+                handle.cancel();
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct BuildHandle {
+    request_id: Option<RequestId>,
+    cancelled: AtomicBool,
+    process_id: Mutex<Option<u32>>,
+}
+
+impl BuildHandle {
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        if let Some(process_id) = *self.process_id.lock().unwrap() {
+            kill_process_tree(process_id);
+        }
+    }
+
+    /// Remembers the running process so that it can be killed when the build gets cancelled.
+    pub(super) fn attach(&self, process_id: u32) {
+        *self.process_id.lock().unwrap() = Some(process_id);
+
+        // The build might have been cancelled before the process was attached.
+        if self.is_cancelled() {
+            kill_process_tree(process_id);
+        }
+    }
+
+    pub(super) fn detach(&self) {
+        *self.process_id.lock().unwrap() = None;
+    }
+}
+
+/// Kills a process including its children (e.g. the TeX engine started by `latexmk`).
+/// On Unix, build steps run in their own process group, which is terminated as a whole.
+fn kill_process_tree(process_id: u32) {
+    let mut command = if cfg!(windows) {
+        let mut command = std::process::Command::new("taskkill");
+        command.args(["/PID", &process_id.to_string(), "/T", "/F"]);
+        command
+    } else {
+        let mut command = std::process::Command::new("kill");
+        command.args(["-TERM", &format!("-{process_id}")]);
+        command
+    };
+
+    if let Err(why) = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+    {
+        log::error!("Failed to kill process {process_id}: {why}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use lsp_server::RequestId;
+    use lsp_types::Url;
+
+    use super::BuildManager;
+
+    fn uri(name: &str) -> Url {
+        Url::parse("file:///texlab/").unwrap().join(name).unwrap()
+    }
+
+    #[test]
+    fn test_same_root() {
+        let manager = BuildManager::default();
+        let (first, first_lock) = manager.start(&uri("main.tex"), None);
+        let _guard = first_lock.lock().unwrap();
+
+        let (second, second_lock) = manager.start(&uri("main.tex"), None);
+        assert!(first.is_cancelled());
+        assert!(!second.is_cancelled());
+        assert!(Arc::ptr_eq(&first_lock, &second_lock));
+        assert!(second_lock.try_lock().is_err());
+    }
+
+    #[test]
+    fn test_other_root() {
+        let manager = BuildManager::default();
+        let (first, first_lock) = manager.start(&uri("foo.tex"), None);
+        let _guard = first_lock.lock().unwrap();
+
+        let (_, second_lock) = manager.start(&uri("bar.tex"), None);
+        assert!(!first.is_cancelled());
+        assert!(second_lock.try_lock().is_ok());
+    }
+
+    #[test]
+    fn test_cancel_root() {
+        let manager = BuildManager::default();
+        let (foo, _) = manager.start(&uri("foo.tex"), None);
+        let (bar, _) = manager.start(&uri("bar.tex"), None);
+
+        manager.cancel(Some(&uri("foo.tex")));
+        assert!(foo.is_cancelled());
+        assert!(!bar.is_cancelled());
+
+        manager.cancel(None);
+        assert!(bar.is_cancelled());
+    }
+
+    #[test]
+    fn test_cancel_request() {
+        let manager = BuildManager::default();
+        let (foo, _) = manager.start(&uri("foo.tex"), Some(RequestId::from(1)));
+        let (bar, _) = manager.start(&uri("bar.tex"), Some(RequestId::from(2)));
+
+        manager.cancel_request(&RequestId::from(1));
+        assert!(foo.is_cancelled());
+        assert!(!bar.is_cancelled());
+    }
+
+    #[test]
+    fn test_finish() {
+        let manager = BuildManager::default();
+        let (handle, lock) = manager.start(&uri("main.tex"), None);
+        manager.finish(&uri("main.tex"), &handle, lock);
+
+        manager.cancel(None);
+        assert!(!handle.is_cancelled());
+        assert!(manager.locks.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_finish_waiting() {
+        let manager = BuildManager::default();
+        let (first, first_lock) = manager.start(&uri("main.tex"), None);
+        let (_, second_lock) = manager.start(&uri("main.tex"), None);
+        manager.finish(&uri("main.tex"), &first, first_lock);
+
+        let locks = manager.locks.lock().unwrap();
+        assert!(Arc::ptr_eq(&locks[&uri("main.tex")], &second_lock));
+    }
+}
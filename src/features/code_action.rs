@@ -0,0 +1,75 @@
+mod bib;
+mod tex;
+
+use std::collections::HashMap;
+
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, Range, TextEdit, Url, WorkspaceEdit,
+};
+use rowan::TextRange;
+
+use crate::{
+    db::{diagnostics::Diagnostic, Workspace},
+    util::{line_index::LineIndex, line_index_ext::LineIndexExt},
+    Db,
+};
+
+pub fn find_all(
+    db: &dyn Db,
+    uri: &Url,
+    range: Range,
+    diagnostics: &[lsp_types::Diagnostic],
+) -> Option<Vec<CodeActionOrCommand>> {
+    let document = Workspace::get(db).lookup_uri(db, uri)?;
+    let line_index = document.contents(db).line_index(db);
+
+    let mut builder = CodeActionBuilder {
+        uri: document.location(db).uri(db),
+        line_index,
+        diagnostics,
+        actions: Vec::new(),
+    };
+
+    let range = line_index.offset_lsp_range(range);
+    tex::find_quick_fixes(db, document, range, &mut builder);
+    bib::find_quick_fixes(db, document, range, &mut builder);
+    Some(builder.actions)
+}
+
+struct CodeActionBuilder<'a> {
+    uri: &'a Url,
+    line_index: &'a LineIndex,
+    diagnostics: &'a [lsp_types::Diagnostic],
+    actions: Vec<CodeActionOrCommand>,
+}
+
+impl<'a> CodeActionBuilder<'a> {
+    pub fn push_quick_fix(
+        &mut self,
+        diagnostic: &Diagnostic,
+        title: String,
+        delete: TextRange,
+        insert: String,
+    ) {
+        let diagnostics = self
+            .diagnostics
+            .iter()
+            .filter(|other| other.range == diagnostic.range && other.message == diagnostic.message)
+            .cloned()
+            .collect();
+
+        let edit = TextEdit::new(self.line_index.line_col_lsp_range(delete), insert);
+        let mut changes = HashMap::default();
+        changes.insert(self.uri.clone(), vec![edit]);
+
+        self.actions
+            .push(CodeActionOrCommand::CodeAction(CodeAction {
+                title,
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(diagnostics),
+                edit: Some(WorkspaceEdit::new(changes)),
+                is_preferred: Some(true),
+                ..CodeAction::default()
+            }));
+    }
+}
@@ -0,0 +1,53 @@
+use rowan::TextRange;
+
+use crate::{
+    db::{
+        diagnostics::{self, BibCode, DiagnosticCode},
+        Document,
+    },
+    util::line_index_ext::LineIndexExt,
+    Db,
+};
+
+use super::CodeActionBuilder;
+
+pub(super) fn find_quick_fixes(
+    db: &dyn Db,
+    document: Document,
+    range: TextRange,
+    builder: &mut CodeActionBuilder,
+) {
+    let line_index = document.contents(db).line_index(db);
+    for diagnostic in diagnostics::bib::collect(db, document) {
+        let diagnostic_range = line_index.offset_lsp_range(diagnostic.range);
+        if diagnostic_range.intersect(range).is_none() {
+            continue;
+        }
+
+        let code = match diagnostic.code {
+            DiagnosticCode::Bib(code) => code,
+            _ => continue,
+        };
+
+        let end = TextRange::empty(diagnostic_range.end());
+        match code {
+            BibCode::ExpectingLCurly => {
+                builder.push_quick_fix(diagnostic, "Insert \"{\"".into(), end, "{".into());
+            }
+            BibCode::ExpectingRCurly => {
+                builder.push_quick_fix(diagnostic, "Insert \"}\"".into(), end, "}".into());
+            }
+            BibCode::ExpectingEq => {
+                builder.push_quick_fix(diagnostic, "Insert \"=\"".into(), end, " =".into());
+            }
+            BibCode::ExpectingKey
+            | BibCode::ExpectingFieldValue
+            | BibCode::UnusedEntry
+            | BibCode::UnknownEntryType
+            | BibCode::UnknownField
+            | BibCode::MissingRequiredField
+            | BibCode::DuplicateEntry
+            | BibCode::LikelyDuplicateEntry => {}
+        };
+    }
+}
@@ -0,0 +1,101 @@
+use rowan::{ast::AstNode, TextRange};
+
+use crate::{
+    db::{
+        diagnostics::{self, Diagnostic, DiagnosticCode, TexCode},
+        Document,
+    },
+    syntax::latex,
+    util::line_index_ext::LineIndexExt,
+    Db,
+};
+
+use super::CodeActionBuilder;
+
+pub(super) fn find_quick_fixes(
+    db: &dyn Db,
+    document: Document,
+    range: TextRange,
+    builder: &mut CodeActionBuilder,
+) -> Option<()> {
+    let root = document.parse(db).as_tex()?.root(db);
+    let line_index = document.contents(db).line_index(db);
+    for diagnostic in diagnostics::tex::collect(db, document) {
+        let diagnostic_range = line_index.offset_lsp_range(diagnostic.range);
+        let code = match diagnostic.code {
+            DiagnosticCode::Tex(code) => code,
+            _ => continue,
+        };
+
+        // A mismatched environment is only reported on the name of `\begin`,
+        // but the fix should also be offered on the `\end` command.
+        let environment = match code {
+            TexCode::MismatchedEnvironment => find_environment(&root, diagnostic_range),
+            _ => None,
+        };
+
+        let applicable_range = environment
+            .as_ref()
+            .map_or(diagnostic_range, |environment| {
+                latex::small_range(environment)
+            });
+
+        if applicable_range.intersect(range).is_none() {
+            continue;
+        }
+
+        match code {
+            TexCode::UnexpectedRCurly => {
+                builder.push_quick_fix(
+                    diagnostic,
+                    "Remove unexpected \"}\"".into(),
+                    diagnostic_range,
+                    String::new(),
+                );
+            }
+            TexCode::RCurlyInserted => {
+                builder.push_quick_fix(
+                    diagnostic,
+                    "Insert missing \"}\"".into(),
+                    diagnostic_range,
+                    "}".into(),
+                );
+            }
+            TexCode::MismatchedEnvironment => {
+                if let Some(environment) = &environment {
+                    fix_mismatched_environment(environment, diagnostic, builder);
+                }
+            }
+            TexCode::UndefinedLabel
+            | TexCode::DuplicateLabel
+            | TexCode::UnusedLabel
+            | TexCode::UndefinedCitation => {}
+        };
+    }
+
+    Some(())
+}
+
+fn find_environment(root: &latex::SyntaxNode, range: TextRange) -> Option<latex::Environment> {
+    root.token_at_offset(range.start())
+        .right_biased()?
+        .parent_ancestors()
+        .find_map(latex::Environment::cast)
+}
+
+fn fix_mismatched_environment(
+    environment: &latex::Environment,
+    diagnostic: &Diagnostic,
+    builder: &mut CodeActionBuilder,
+) -> Option<()> {
+    let begin_name = environment.begin()?.name()?.key()?.to_string();
+    let end_name = environment.end()?.name()?.key()?;
+    builder.push_quick_fix(
+        diagnostic,
+        format!("Rename \"\\end{{{end_name}}}\" to \"\\end{{{begin_name}}}\""),
+        latex::small_range(&end_name),
+        begin_name,
+    );
+
+    Some(())
+}
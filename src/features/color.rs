@@ -0,0 +1,216 @@
+use std::str::FromStr;
+
+use lsp_types::{Color, ColorInformation, ColorPresentation, Range, TextEdit, Url};
+use rowan::{ast::AstNode, TextRange};
+use rustc_hash::FxHashMap;
+
+use crate::{
+    db::Workspace,
+    syntax::latex::{self, HasCurly},
+    util::{lang_data::LANGUAGE_DATA, line_index_ext::LineIndexExt},
+    Db,
+};
+
+pub fn find_all(db: &dyn Db, uri: &Url) -> Option<Vec<ColorInformation>> {
+    let workspace = Workspace::get(db);
+    let document = workspace.lookup_uri(db, uri)?;
+    let line_index = document.contents(db).line_index(db);
+    let root = document.parse(db).as_tex()?.root(db);
+
+    let mut user_colors = FxHashMap::default();
+    for child in workspace.related(db, document) {
+        if let Some(data) = child.parse(db).as_tex() {
+            for definition in data
+                .root(db)
+                .descendants()
+                .filter_map(latex::ColorDefinition::cast)
+            {
+                if let Some((name, (_, _, color))) = definition
+                    .name()
+                    .and_then(|name| name.key())
+                    .zip(parse_definition(&definition))
+                {
+                    user_colors.insert(name.to_string(), color);
+                }
+            }
+        }
+    }
+
+    let mut results = Vec::new();
+    for node in root.descendants() {
+        let result = match latex::ColorDefinition::cast(node.clone()) {
+            Some(definition) => {
+                parse_definition(&definition).map(|(_, range, color)| (range, color))
+            }
+            None => latex::ColorReference::cast(node).and_then(|reference| {
+                let name = reference.name()?.key()?;
+                let name_text = name.to_string();
+                let color = user_colors
+                    .get(&name_text)
+                    .cloned()
+                    .or_else(|| find_predefined(&name_text))?;
+
+                Some((latex::small_range(&name), color))
+            }),
+        };
+
+        if let Some((range, color)) = result {
+            results.push(ColorInformation {
+                range: line_index.line_col_lsp_range(range),
+                color,
+            });
+        }
+    }
+
+    Some(results)
+}
+
+pub fn find_presentations(
+    db: &dyn Db,
+    uri: &Url,
+    range: Range,
+    color: Color,
+) -> Option<Vec<ColorPresentation>> {
+    let document = Workspace::get(db).lookup_uri(db, uri)?;
+    let line_index = document.contents(db).line_index(db);
+    let root = document.parse(db).as_tex()?.root(db);
+
+    // Only the spec of a definition can be rewritten since references use the name of the color.
+    let range = line_index.offset_lsp_range(range);
+    let (model, spec_range, _) = root
+        .descendants()
+        .filter_map(latex::ColorDefinition::cast)
+        .filter_map(|definition| parse_definition(&definition))
+        .find(|(_, spec_range, _)| *spec_range == range)?;
+
+    let text = model.format(color);
+    Some(vec![ColorPresentation {
+        label: text.clone(),
+        text_edit: Some(TextEdit::new(
+            line_index.line_col_lsp_range(spec_range),
+            text,
+        )),
+        additional_text_edits: None,
+    }])
+}
+
+fn parse_definition(definition: &latex::ColorDefinition) -> Option<(ColorModel, TextRange, Color)> {
+    let model = ColorModel::from_str(&definition.model()?.key()?.to_string()).ok()?;
+    let spec = definition.spec()?;
+    let range = TextRange::new(
+        spec.left_curly()?.text_range().end(),
+        spec.right_curly()?.text_range().start(),
+    );
+
+    let color = model.parse(&spec.content_text()?)?;
+    Some((model, range, color))
+}
+
+fn find_predefined(name: &str) -> Option<Color> {
+    let color = LANGUAGE_DATA
+        .colors
+        .iter()
+        .find(|color| color.name == name)?;
+
+    ColorModel::from_str(&color.model).ok()?.parse(&color.spec)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum ColorModel {
+    Gray,
+    Rgb,
+    RgbInteger,
+    Html,
+    Cmyk,
+}
+
+impl FromStr for ColorModel {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gray" => Ok(Self::Gray),
+            "rgb" => Ok(Self::Rgb),
+            "RGB" => Ok(Self::RgbInteger),
+            "HTML" => Ok(Self::Html),
+            "cmyk" => Ok(Self::Cmyk),
+            _ => Err(()),
+        }
+    }
+}
+
+impl ColorModel {
+    /// Converts a color spec like `0.5,0,1` to RGB using the formulas of `xcolor`.
+    fn parse(self, spec: &str) -> Option<Color> {
+        let [red, green, blue] = if self == Self::Html {
+            let spec = spec.trim();
+            if spec.len() != 6 {
+                return None;
+            }
+
+            let value = u32::from_str_radix(spec, 16).ok()?;
+            [value >> 16, value >> 8, value].map(|component| (component & 0xFF) as f32 / 255.0)
+        } else {
+            let values = spec
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|value| !value.is_empty())
+                .map(|value| value.parse::<f32>().ok())
+                .collect::<Option<Vec<_>>>()?;
+
+            match (self, values.as_slice()) {
+                (Self::Gray, &[gray]) => [gray; 3],
+                (Self::Rgb, &[red, green, blue]) => [red, green, blue],
+                (Self::RgbInteger, &[red, green, blue]) => {
+                    [red, green, blue].map(|component| component / 255.0)
+                }
+                (Self::Cmyk, &[cyan, magenta, yellow, black]) => {
+                    [cyan, magenta, yellow].map(|component| 1.0 - (component + black).min(1.0))
+                }
+                _ => return None,
+            }
+        };
+
+        Some(Color {
+            red: red.clamp(0.0, 1.0),
+            green: green.clamp(0.0, 1.0),
+            blue: blue.clamp(0.0, 1.0),
+            alpha: 1.0,
+        })
+    }
+
+    fn format(self, color: Color) -> String {
+        let Color {
+            red, green, blue, ..
+        } = color;
+
+        match self {
+            Self::Gray => format_decimal(0.3 * red + 0.59 * green + 0.11 * blue),
+            Self::Rgb => [red, green, blue].map(format_decimal).join(","),
+            Self::RgbInteger => [red, green, blue]
+                .map(|component| to_byte(component).to_string())
+                .join(","),
+            Self::Html => format!(
+                "{:02X}{:02X}{:02X}",
+                to_byte(red),
+                to_byte(green),
+                to_byte(blue)
+            ),
+            Self::Cmyk => {
+                let [cyan, magenta, yellow] = [red, green, blue].map(|component| 1.0 - component);
+                let black = cyan.min(magenta).min(yellow);
+                [cyan - black, magenta - black, yellow - black, black]
+                    .map(format_decimal)
+                    .join(",")
+            }
+        }
+    }
+}
+
+fn to_byte(component: f32) -> u8 {
+    (component.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+fn format_decimal(value: f32) -> String {
+    let text = format!("{:.3}", value.clamp(0.0, 1.0));
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
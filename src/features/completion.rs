@@ -12,6 +12,7 @@ mod field;
 mod glossary_ref;
 mod import;
 mod include;
+mod key_value;
 mod label;
 mod theorem;
 mod tikz_library;
@@ -33,6 +34,7 @@ pub fn complete(db: &dyn Db, uri: &Url, position: Position) -> Option<Completion
     argument::complete(&context, &mut builder);
     citation::complete(&context, &mut builder);
     import::complete(&context, &mut builder);
+    key_value::complete(&context, &mut builder);
     color::complete(&context, &mut builder);
     color_model::complete(&context, &mut builder);
     acronym_ref::complete(&context, &mut builder);
@@ -234,6 +234,30 @@ impl<'db> CompletionBuilder<'db> {
         Some(())
     }
 
+    pub fn option_key(&mut self, range: TextRange, name: &'db str) -> Option<()> {
+        let score = self.matcher.fuzzy_match(name, &self.text_pattern)?;
+        self.items.push(Item {
+            range,
+            data: Data::OptionKey { name },
+            preselect: false,
+            score,
+        });
+
+        Some(())
+    }
+
+    pub fn option_value(&mut self, range: TextRange, name: &'db str) -> Option<()> {
+        let score = self.matcher.fuzzy_match(name, &self.text_pattern)?;
+        self.items.push(Item {
+            range,
+            data: Data::OptionValue { name },
+            preselect: false,
+            score,
+        });
+
+        Some(())
+    }
+
     pub fn component_command(
         &mut self,
         range: TextRange,
@@ -356,25 +380,11 @@ impl<'db> CompletionBuilder<'db> {
         Some(())
     }
 
-    pub fn label(
-        &mut self,
-        range: TextRange,
-        name: &'db str,
-        kind: Structure,
-        header: Option<String>,
-        footer: Option<String>,
-        text: String,
-    ) -> Option<()> {
-        let score = self.matcher.fuzzy_match(&text, &self.text_pattern)?;
+    pub fn label(&mut self, range: TextRange, name: &'db str, label: LabelData) -> Option<()> {
+        let score = self.matcher.fuzzy_match(&label.text, &self.text_pattern)?;
         self.items.push(Item {
             range,
-            data: Data::Label {
-                name,
-                kind,
-                header,
-                footer,
-                text,
-            },
+            data: Data::Label { name, label },
             preselect: false,
             score,
         });
@@ -394,11 +404,16 @@ impl<'db> CompletionBuilder<'db> {
         Some(())
     }
 
-    pub fn user_command(&mut self, range: TextRange, name: &'db str) -> Option<()> {
+    pub fn user_command(
+        &mut self,
+        range: TextRange,
+        name: &'db str,
+        snippet: Option<String>,
+    ) -> Option<()> {
         let score = self.matcher.fuzzy_match(name, &self.text_pattern[1..])?;
         self.items.push(Item {
             range,
-            data: Data::UserCommand { name },
+            data: Data::UserCommand { name, snippet },
             preselect: false,
             score,
         });
@@ -425,6 +440,7 @@ impl<'db> CompletionBuilder<'db> {
             .sorted_by(|a, b| {
                 b.preselect
                     .cmp(&a.preselect)
+                    .then_with(|| b.data.priority().cmp(&a.data.priority()))
                     .then_with(|| b.score.cmp(&a.score))
                     .then_with(|| a.data.label().cmp(b.data.label()))
             })
@@ -565,6 +581,18 @@ impl<'db> CompletionBuilder<'db> {
                 text_edit: Some(TextEdit::new(range, name.into()).into()),
                 ..CompletionItem::default()
             },
+            Data::OptionKey { name } => CompletionItem {
+                label: name.into(),
+                kind: Some(Structure::OptionKey.completion_kind()),
+                text_edit: Some(TextEdit::new(range, name.into()).into()),
+                ..CompletionItem::default()
+            },
+            Data::OptionValue { name } => CompletionItem {
+                label: name.into(),
+                kind: Some(Structure::Argument.completion_kind()),
+                text_edit: Some(TextEdit::new(range, name.into()).into()),
+                ..CompletionItem::default()
+            },
             Data::ColorModel { name } => CompletionItem {
                 label: name.into(),
                 kind: Some(Structure::ColorModel.completion_kind()),
@@ -589,29 +617,31 @@ impl<'db> CompletionBuilder<'db> {
                 text_edit: Some(TextEdit::new(range, name).into()),
                 ..CompletionItem::default()
             },
-            Data::Label {
-                name,
-                kind,
-                header,
-                footer,
-                text,
-            } => CompletionItem {
+            Data::Label { name, label } => CompletionItem {
                 label: name.into(),
-                kind: Some(kind.completion_kind()),
-                detail: header,
-                documentation: footer.map(Documentation::String),
-                sort_text: Some(text.clone()),
-                filter_text: Some(text),
+                kind: Some(label.kind.completion_kind()),
+                detail: label.header,
+                documentation: label.footer.map(Documentation::String),
+                sort_text: Some(label.text.clone()),
+                filter_text: Some(label.text),
                 text_edit: Some(TextEdit::new(range, name.into()).into()),
                 ..CompletionItem::default()
             },
-            Data::UserCommand { name } => {
+            Data::UserCommand { name, snippet } => {
                 let detail = "user-defined".into();
                 let name = &name[1..];
-                CompletionItem {
-                    kind: Some(Structure::Command.completion_kind()),
-                    text_edit: Some(TextEdit::new(range, name.into()).into()),
-                    ..CompletionItem::new_simple(name.into(), detail)
+                match snippet.filter(|_| self.snippets) {
+                    Some(snippet) => CompletionItem {
+                        kind: Some(Structure::Command.completion_kind()),
+                        text_edit: Some(TextEdit::new(range, format!("{name}{snippet}")).into()),
+                        insert_text_format: Some(InsertTextFormat::SNIPPET),
+                        ..CompletionItem::new_simple(name.into(), detail)
+                    },
+                    None => CompletionItem {
+                        kind: Some(Structure::Command.completion_kind()),
+                        text_edit: Some(TextEdit::new(range, name.into()).into()),
+                        ..CompletionItem::new_simple(name.into(), detail)
+                    },
                 }
             }
             Data::UserEnvironment { name } => {
@@ -672,6 +702,16 @@ impl<'db> CompletionBuilder<'db> {
     }
 }
 
+/// Describes the object a label refers to.
+#[derive(Debug, Clone)]
+pub struct LabelData {
+    pub kind: Structure,
+    pub header: Option<String>,
+    pub footer: Option<String>,
+    pub text: String,
+    pub priority: u8,
+}
+
 #[derive(Debug, Clone)]
 struct Item<'db> {
     range: TextRange,
@@ -721,6 +761,12 @@ enum Data<'db> {
     ColorModel {
         name: &'db str,
     },
+    OptionKey {
+        name: &'db str,
+    },
+    OptionValue {
+        name: &'db str,
+    },
     GlossaryEntry {
         name: String,
     },
@@ -732,13 +778,11 @@ enum Data<'db> {
     },
     Label {
         name: &'db str,
-        kind: Structure,
-        header: Option<String>,
-        footer: Option<String>,
-        text: String,
+        label: LabelData,
     },
     UserCommand {
         name: &'db str,
+        snippet: Option<String>,
     },
     UserEnvironment {
         name: &'db str,
@@ -762,15 +806,25 @@ impl<'db> Data<'db> {
             Self::Package { name } => name,
             Self::Color { name } => name,
             Self::ColorModel { name } => name,
+            Self::OptionKey { name } => name,
+            Self::OptionValue { name } => name,
             Self::GlossaryEntry { name } => name,
             Self::File { name } => name,
             Self::Directory { name } => name,
             Self::Label { name, .. } => name,
-            Self::UserCommand { name } => name,
+            Self::UserCommand { name, .. } => name,
             Self::UserEnvironment { name } => name,
             Self::TikzLibrary { name } => name,
         }
     }
+
+    /// Items with a higher priority are listed first regardless of their score.
+    pub fn priority(&self) -> u8 {
+        match self {
+            Self::Label { label, .. } => label.priority,
+            _ => 0,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -14,8 +14,8 @@ pub fn complete<'db>(
     let (_, range, group) = context.find_curly_group_word()?;
     latex::ColorReference::cast(group.syntax().parent()?)?;
 
-    for name in &LANGUAGE_DATA.colors {
-        builder.color(range, name);
+    for color in &LANGUAGE_DATA.colors {
+        builder.color(range, &color.name);
     }
 
     Some(())
@@ -0,0 +1,177 @@
+use rowan::{ast::AstNode, TextRange, TextSize};
+
+use crate::{
+    syntax::latex,
+    util::{
+        cursor::CursorContext,
+        lang_data::{KeyValueContextKind, LANGUAGE_DATA},
+    },
+};
+
+use super::builder::CompletionBuilder;
+
+pub fn complete<'db>(
+    context: &'db CursorContext,
+    builder: &mut CompletionBuilder<'db>,
+) -> Option<()> {
+    let token = context.cursor.as_tex()?;
+    let group = token.parent_ancestors().find(|node| {
+        matches!(
+            node.kind(),
+            latex::CURLY_GROUP
+                | latex::BRACK_GROUP
+                | latex::MIXED_GROUP
+                | latex::CURLY_GROUP_KEY_VALUE
+                | latex::BRACK_GROUP_KEY_VALUE
+        )
+    })?;
+
+    let (kind, names) = find_context(&group)?;
+
+    let close = group
+        .children_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| matches!(token.kind(), latex::R_CURLY | latex::R_BRACK))
+        .last();
+
+    let group_range = group.text_range();
+    if context.offset <= group_range.start()
+        || close.map_or(false, |close| context.offset > close.text_range().start())
+    {
+        return None;
+    }
+
+    let text = group.to_string();
+    let offset = usize::from(context.offset - group_range.start());
+    let pair = find_key_value_cursor(&text, offset)?;
+    let range = TextRange::new(
+        group_range.start() + TextSize::try_from(pair.start).ok()?,
+        group_range.start() + TextSize::try_from(pair.end).ok()?,
+    );
+
+    for name in &names {
+        for (key, values) in LANGUAGE_DATA.key_value_options(kind, name) {
+            match pair.key {
+                Some(current_key) if current_key == key => {
+                    for value in values {
+                        builder.option_value(range, value);
+                    }
+                }
+                Some(_) => {}
+                None => {
+                    builder.option_key(range, key);
+                }
+            };
+        }
+    }
+
+    Some(())
+}
+
+fn find_context(group: &latex::SyntaxNode) -> Option<(KeyValueContextKind, Vec<String>)> {
+    let parent = group.parent()?;
+    if let Some(include) = latex::Include::cast(parent.clone()) {
+        if group.kind() != latex::BRACK_GROUP_KEY_VALUE {
+            return None;
+        }
+
+        let kind = match parent.kind() {
+            latex::PACKAGE_INCLUDE => KeyValueContextKind::Package,
+            latex::CLASS_INCLUDE => KeyValueContextKind::Class,
+            _ => {
+                let name = command_name(&include.command()?);
+                return Some((KeyValueContextKind::Command, vec![name]));
+            }
+        };
+
+        let names = include
+            .path_list()?
+            .keys()
+            .map(|key| key.to_string())
+            .collect();
+
+        Some((kind, names))
+    } else if let Some(begin) = latex::Begin::cast(parent.clone()) {
+        if group.kind() != latex::BRACK_GROUP {
+            return None;
+        }
+
+        let name = begin.name()?.key()?.to_string();
+        Some((KeyValueContextKind::Environment, vec![name]))
+    } else if let Some(command) = latex::GenericCommand::cast(parent) {
+        // Commands like `\hypersetup{...}` and `\draw[...]` take their options in the first group.
+        let first_group = command
+            .syntax()
+            .children()
+            .find(|node| matches!(node.kind(), latex::CURLY_GROUP | latex::MIXED_GROUP))?;
+
+        let is_options = first_group == *group
+            && (group.kind() == latex::CURLY_GROUP
+                || latex::MixedGroup::cast(group.clone())?.left_delim()?.kind() == latex::L_BRACK);
+
+        if !is_options {
+            return None;
+        }
+
+        Some((
+            KeyValueContextKind::Command,
+            vec![command_name(&command.name()?)],
+        ))
+    } else {
+        None
+    }
+}
+
+fn command_name(token: &latex::SyntaxToken) -> String {
+    let name = &token.text()[1..];
+    name.strip_suffix('*').unwrap_or(name).to_string()
+}
+
+/// The key or value at the cursor within the text of a group.
+struct KeyValueCursor<'a> {
+    /// The key if the cursor is located inside of a value.
+    key: Option<&'a str>,
+    start: usize,
+    end: usize,
+}
+
+fn find_key_value_cursor(text: &str, offset: usize) -> Option<KeyValueCursor> {
+    let before = text.get(1..offset)?;
+
+    let mut depth = 0;
+    let mut pair_start = 1;
+    let mut equality_sign = None;
+    for (i, c) in before.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth > 0 => depth -= 1,
+            ',' if depth == 0 => {
+                pair_start = i + 2;
+                equality_sign = None;
+            }
+            '=' if depth == 0 && equality_sign.is_none() => equality_sign = Some(i + 1),
+            _ => {}
+        };
+    }
+
+    // Values inside of braces are arbitrary text.
+    if depth > 0 {
+        return None;
+    }
+
+    let (key, start) = match equality_sign {
+        Some(equality_sign) => (
+            Some(text[pair_start..equality_sign].trim()),
+            equality_sign + 1,
+        ),
+        None => (None, pair_start),
+    };
+
+    let start = offset - text[start..offset].trim_start().len();
+    let end = text[offset..]
+        .find(|c| matches!(c, ',' | '=' | ']' | '}' | '{' | '\r' | '\n'))
+        .map_or(text.len(), |i| offset + i);
+
+    let end = (start + text[start..end].trim_end().len()).max(offset);
+    Some(KeyValueCursor { key, start, end })
+}
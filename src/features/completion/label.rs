@@ -5,13 +5,30 @@ use crate::{
     util::{self, cursor::CursorContext, label::LabeledObject, lsp_enums::Structure},
 };
 
-use super::builder::CompletionBuilder;
+use super::builder::{CompletionBuilder, LabelData};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum ReferenceKind {
+    /// `\ref` and similar commands which only print the number.
+    Plain,
+    /// `\eqref`, which is meant for equations.
+    Equation,
+    /// Commands like `\cref` and `\autoref` which print the kind of the labeled object.
+    Typed,
+}
 
 pub fn complete<'db>(
     context: &'db CursorContext,
     builder: &mut CompletionBuilder<'db>,
 ) -> Option<()> {
-    let (range, is_math) = find_reference(context).or_else(|| find_reference_range(context))?;
+    let (range, reference_kind) =
+        find_reference(context).or_else(|| find_reference_range(context))?;
+
+    let current_section = context
+        .cursor
+        .as_tex()
+        .and_then(|token| token.parent_ancestors().find_map(latex::Section::cast))
+        .map(|section| latex::small_range(&section));
 
     let db = context.db;
     for document in context.related() {
@@ -22,6 +39,17 @@ pub fn complete<'db>(
                 .iter()
                 .filter(|label| label.origin(db).as_definition().is_some())
             {
+                // Labels from the current section and file are more likely to be referenced.
+                let mut priority = 0;
+                if document == context.document {
+                    priority += 1;
+                    if current_section
+                        .map_or(false, |section| section.contains_range(label.range(db)))
+                    {
+                        priority += 1;
+                    }
+                }
+
                 match util::label::render(db, document, *label) {
                     Some(rendered_label) => {
                         let kind = match &rendered_label.object {
@@ -32,11 +60,18 @@ pub fn complete<'db>(
                             LabeledObject::EnumItem => Structure::Item,
                         };
 
-                        if is_math && kind != Structure::Equation {
-                            continue;
+                        if reference_kind == ReferenceKind::Equation && kind == Structure::Equation
+                        {
+                            priority += 3;
                         }
 
-                        let header = rendered_label.detail(db);
+                        let header = match reference_kind {
+                            ReferenceKind::Typed => Some(rendered_label.short_reference(db)),
+                            ReferenceKind::Plain | ReferenceKind::Equation => {
+                                rendered_label.detail(db)
+                            }
+                        };
+
                         let footer = match &rendered_label.object {
                             LabeledObject::Float { caption, .. } => Some(caption.clone()),
                             _ => None,
@@ -48,14 +83,30 @@ pub fn complete<'db>(
                             rendered_label.reference(db)
                         );
 
-                        builder.label(range, label.name(db).text(db), kind, header, footer, text);
+                        builder.label(
+                            range,
+                            label.name(db).text(db),
+                            LabelData {
+                                kind,
+                                header,
+                                footer,
+                                text,
+                                priority,
+                            },
+                        );
                     }
                     None => {
-                        let kind = Structure::Label;
-                        let header = None;
-                        let footer = None;
-                        let text = label.name(db).text(db).clone();
-                        builder.label(range, label.name(db).text(db), kind, header, footer, text);
+                        builder.label(
+                            range,
+                            label.name(db).text(db),
+                            LabelData {
+                                kind: Structure::Label,
+                                header: None,
+                                footer: None,
+                                text: label.name(db).text(db).clone(),
+                                priority,
+                            },
+                        );
                     }
                 }
             }
@@ -65,15 +116,22 @@ pub fn complete<'db>(
     Some(())
 }
 
-fn find_reference(context: &CursorContext) -> Option<(TextRange, bool)> {
+fn find_reference(context: &CursorContext) -> Option<(TextRange, ReferenceKind)> {
     let (_, range, group) = context.find_curly_group_word_list()?;
     let reference = latex::LabelReference::cast(group.syntax().parent()?)?;
-    let is_math = reference.command()?.text() == "\\eqref";
-    Some((range, is_math))
+    let kind = match reference.command()?.text() {
+        "\\eqref" => ReferenceKind::Equation,
+        "\\autoref" | "\\cref" | "\\cref*" | "\\Cref" | "\\Cref*" | "\\vref" | "\\Vref" => {
+            ReferenceKind::Typed
+        }
+        _ => ReferenceKind::Plain,
+    };
+
+    Some((range, kind))
 }
 
-fn find_reference_range(context: &CursorContext) -> Option<(TextRange, bool)> {
+fn find_reference_range(context: &CursorContext) -> Option<(TextRange, ReferenceKind)> {
     let (_, range, group) = context.find_curly_group_word()?;
     latex::LabelReferenceRange::cast(group.syntax().parent()?)?;
-    Some((range, false))
+    Some((range, ReferenceKind::Typed))
 }
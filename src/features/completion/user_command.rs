@@ -1,4 +1,10 @@
-use crate::util::cursor::CursorContext;
+use rowan::ast::AstNode;
+use rustc_hash::FxHashMap;
+
+use crate::{
+    syntax::latex,
+    util::{command_signature::CommandSignature, cursor::CursorContext},
+};
 
 use super::builder::CompletionBuilder;
 
@@ -10,6 +16,22 @@ pub fn complete<'db>(
     let token = context.cursor.as_tex()?;
 
     let db = context.db;
+    let mut snippets = FxHashMap::default();
+    for document in context.related() {
+        if let Some(data) = document.parse(db).as_tex() {
+            for signature in data
+                .root(db)
+                .descendants()
+                .filter_map(latex::CommandDefinition::cast)
+                .filter_map(|definition| CommandSignature::parse(&definition))
+            {
+                if let Some(snippet) = signature.snippet() {
+                    snippets.entry(signature.name).or_insert(snippet);
+                }
+            }
+        }
+    }
+
     for document in context.related() {
         if let Some(data) = document.parse(db).as_tex() {
             let text = document.contents(db).text(db);
@@ -21,7 +43,8 @@ pub fn complete<'db>(
                 .filter(|range| *range != token.text_range())
                 .map(|range| &text[std::ops::Range::<usize>::from(range)])
             {
-                builder.user_command(range, name);
+                let snippet = snippets.get(name).cloned();
+                builder.user_command(range, name, snippet);
             }
         }
     }
@@ -1,6 +1,7 @@
 mod command;
 mod document;
 mod entry;
+mod environment;
 mod label;
 mod string;
 
@@ -25,6 +26,7 @@ pub fn goto_definition(
         .or_else(|| document::goto_definition(&context))
         .or_else(|| entry::goto_definition(&context))
         .or_else(|| label::goto_definition(&context))
+        .or_else(|| environment::goto_definition(&context))
         .or_else(|| string::goto_definition(&context))?
         .into_iter()
         .map(|result| {
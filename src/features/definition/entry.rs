@@ -1,11 +1,8 @@
 use rowan::ast::AstNode;
 
 use crate::{
-    syntax::{
-        bibtex::{self, HasName},
-        latex,
-    },
-    util::cursor::CursorContext,
+    syntax::latex,
+    util::{bibliography::entry_keys, cursor::CursorContext},
 };
 
 use super::DefinitionResult;
@@ -26,8 +23,8 @@ pub(super) fn goto_definition(context: &CursorContext) -> Option<Vec<DefinitionR
 
     for document in context.related() {
         if let Some(data) = document.parse(db).as_bib() {
-            for entry in data.root(db).children().filter_map(bibtex::Entry::cast) {
-                if let Some(key) = entry.name_token().filter(|k| k.text() == word.text()) {
+            for (entry, key) in entry_keys(&data.root(db)) {
+                if key.text() == word.text() {
                     return Some(vec![DefinitionResult {
                         origin_selection_range,
                         target: document,
@@ -0,0 +1,41 @@
+use rowan::ast::AstNode;
+
+use crate::{syntax::latex, util::cursor::CursorContext};
+
+use super::DefinitionResult;
+
+pub(super) fn goto_definition(context: &CursorContext) -> Option<Vec<DefinitionResult>> {
+    let db = context.db;
+    let (name_text, origin_selection_range) = context
+        .find_environment_name()
+        .filter(|(name, _)| !name.is_empty())?;
+
+    for document in context.related() {
+        if let Some(data) = document.parse(db).as_tex() {
+            for node in data.root(db).descendants() {
+                let (target_range, name) =
+                    if let Some(definition) = latex::EnvironmentDefinition::cast(node.clone()) {
+                        (latex::small_range(&definition), definition.name())
+                    } else if let Some(definition) = latex::TheoremDefinition::cast(node) {
+                        (latex::small_range(&definition), definition.name())
+                    } else {
+                        continue;
+                    };
+
+                if let Some(name) = name
+                    .and_then(|name| name.key())
+                    .filter(|name| name.to_string() == name_text)
+                {
+                    return Some(vec![DefinitionResult {
+                        origin_selection_range,
+                        target: document,
+                        target_range,
+                        target_selection_range: latex::small_range(&name),
+                    }]);
+                }
+            }
+        }
+    }
+
+    None
+}
@@ -1,4 +1,5 @@
 mod bibtex_internal;
+mod latex_internal;
 mod latexindent;
 
 use lsp_types::{FormattingOptions, TextEdit, Url};
@@ -8,7 +9,10 @@ use crate::{
     BibtexFormatter, Db, LatexFormatter,
 };
 
-use self::{bibtex_internal::format_bibtex_internal, latexindent::format_with_latexindent};
+use self::{
+    bibtex_internal::format_bibtex_internal, latex_internal::format_latex_internal,
+    latexindent::format_with_latexindent,
+};
 
 pub fn format_source_code(
     db: &dyn Db,
@@ -19,13 +23,40 @@ pub fn format_source_code(
     let document = workspace.lookup_uri(db, uri)?;
     match document.language(db) {
         Language::Tex => match workspace.options(db).latex_formatter {
-            LatexFormatter::Texlab => None,
+            LatexFormatter::Texlab => format_latex_internal(db, document, options),
             LatexFormatter::Latexindent => format_with_latexindent(db, document),
         },
         Language::Bib => match workspace.options(db).bibtex_formatter {
             BibtexFormatter::Texlab => format_bibtex_internal(db, document, options),
             BibtexFormatter::Latexindent => format_with_latexindent(db, document),
         },
-        Language::Log | Language::TexlabRoot | Language::Tectonic => None,
+        Language::Log
+        | Language::Blg
+        | Language::TexlabRoot
+        | Language::Tectonic
+        | Language::Latexmkrc => None,
+    }
+}
+
+/// Returns the text of one indentation level.
+fn indent_text(options: &FormattingOptions) -> String {
+    if options.insert_spaces {
+        " ".repeat(options.tab_size as usize)
+    } else {
+        String::from("\t")
     }
 }
+
+/// Returns the maximum line length, where a non-positive setting disables wrapping.
+fn line_length(db: &dyn Db) -> usize {
+    Workspace::get(db)
+        .options(db)
+        .formatter_line_length
+        .map_or(80, |value| {
+            if value <= 0 {
+                usize::MAX
+            } else {
+                value as usize
+            }
+        })
+}
@@ -2,37 +2,21 @@ use lsp_types::{FormattingOptions, TextEdit};
 use rowan::{ast::AstNode, NodeOrToken};
 
 use crate::{
-    db::{Document, Workspace},
+    db::Document,
     syntax::bibtex::{self, HasName, HasType, HasValue},
     util::{line_index::LineIndex, line_index_ext::LineIndexExt},
     Db,
 };
 
+use super::{indent_text, line_length};
+
 pub fn format_bibtex_internal(
     db: &dyn Db,
     document: Document,
     options: &FormattingOptions,
 ) -> Option<Vec<TextEdit>> {
-    let mut indent = String::new();
-
-    if options.insert_spaces {
-        for _ in 0..options.tab_size {
-            indent.push(' ');
-        }
-    } else {
-        indent.push('\t');
-    }
-
-    let line_length = Workspace::get(db)
-        .options(db)
-        .formatter_line_length
-        .map_or(80, |value| {
-            if value <= 0 {
-                usize::MAX
-            } else {
-                value as usize
-            }
-        });
+    let indent = indent_text(options);
+    let line_length = line_length(db);
 
     let line_index = document.contents(db).line_index(db);
     let data = document.parse(db).as_bib()?;
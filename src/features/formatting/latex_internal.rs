@@ -0,0 +1,262 @@
+use lsp_types::{FormattingOptions, TextEdit};
+use rowan::{ast::AstNode, NodeOrToken, TextLen, TextRange};
+
+use crate::{db::Document, syntax::latex, util::line_index_ext::LineIndexExt, Db};
+
+use super::{indent_text, line_length};
+
+pub fn format_latex_internal(
+    db: &dyn Db,
+    document: Document,
+    options: &FormattingOptions,
+) -> Option<Vec<TextEdit>> {
+    let indent = indent_text(options);
+    let line_length = line_length(db);
+
+    let text = document.contents(db).text(db);
+    let line_index = document.contents(db).line_index(db);
+    let data = document.parse(db).as_tex()?;
+
+    let mut formatter = Formatter {
+        indent,
+        line_length,
+        output: String::new(),
+        column: 0,
+        at_line_start: true,
+        pending_whitespace: None,
+    };
+
+    for token in data
+        .root(db)
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+    {
+        formatter.visit_token(token);
+    }
+
+    if &formatter.output == text {
+        return Some(Vec::new());
+    }
+
+    Some(vec![TextEdit {
+        range: line_index.line_col_lsp_range(TextRange::up_to(text.text_len())),
+        new_text: formatter.output,
+    }])
+}
+
+struct Formatter {
+    indent: String,
+    line_length: usize,
+    output: String,
+    column: usize,
+    at_line_start: bool,
+    pending_whitespace: Option<latex::SyntaxToken>,
+}
+
+impl Formatter {
+    fn visit_token(&mut self, token: latex::SyntaxToken) {
+        match token.kind() {
+            latex::WHITESPACE | latex::LINE_BREAK => {
+                let line_breaks: String = token
+                    .text()
+                    .chars()
+                    .filter(|c| matches!(c, '\r' | '\n'))
+                    .collect();
+
+                if !line_breaks.is_empty() {
+                    self.output.push_str(&line_breaks);
+                    self.column = 0;
+                    self.at_line_start = true;
+                }
+
+                self.pending_whitespace = Some(token);
+            }
+            _ if token.text().is_empty() => {}
+            _ => {
+                if self.at_line_start {
+                    let whitespace = self.pending_whitespace.take();
+                    if token.kind() == latex::COMMENT {
+                        // Comment lines keep their original indentation.
+                        let indent = whitespace.as_ref().map_or("", |whitespace| {
+                            whitespace.text().rsplit(['\r', '\n']).next().unwrap()
+                        });
+
+                        self.output.push_str(indent);
+                        self.column += indent.chars().count();
+                    } else {
+                        self.push_indent(indent_level(&token));
+                    }
+
+                    self.at_line_start = false;
+                } else if let Some(whitespace) = self.pending_whitespace.take() {
+                    self.visit_whitespace(&whitespace, &token);
+                }
+
+                // Verbatim content and comments are copied without any modification,
+                // except for the indentation of the line which ends the verbatim environment.
+                let mut text = token.text();
+                if token.kind() == latex::VERBATIM {
+                    let trimmed = text.trim_end_matches([' ', '\t']);
+                    if trimmed.ends_with('\n') {
+                        text = trimmed;
+                        self.at_line_start = true;
+                    }
+                }
+
+                self.output.push_str(text);
+                self.column = match text.rfind('\n') {
+                    Some(index) => text[index + 1..].chars().count(),
+                    None => self.column + text.chars().count(),
+                };
+            }
+        };
+    }
+
+    fn visit_whitespace(&mut self, whitespace: &latex::SyntaxToken, next: &latex::SyntaxToken) {
+        if is_removable(whitespace) {
+            return;
+        }
+
+        if next.kind() == latex::COMMENT {
+            self.output.push_str(whitespace.text());
+            self.column += whitespace.text().chars().count();
+        } else if is_breakable(whitespace)
+            && self.column + 1 + next.text().chars().count() > self.line_length
+        {
+            self.output.push('\n');
+            self.column = 0;
+            self.push_indent(indent_level(next));
+        } else {
+            self.output.push(' ');
+            self.column += 1;
+        }
+    }
+
+    fn push_indent(&mut self, level: usize) {
+        for _ in 0..level {
+            self.output.push_str(&self.indent);
+            self.column += self.indent.chars().count();
+        }
+    }
+}
+
+fn is_group(kind: latex::SyntaxKind) -> bool {
+    matches!(
+        kind,
+        latex::CURLY_GROUP
+            | latex::CURLY_GROUP_WORD
+            | latex::CURLY_GROUP_WORD_LIST
+            | latex::CURLY_GROUP_COMMAND
+            | latex::CURLY_GROUP_KEY_VALUE
+            | latex::BRACK_GROUP
+            | latex::BRACK_GROUP_WORD
+            | latex::BRACK_GROUP_KEY_VALUE
+    )
+}
+
+/// Computes the indentation of a token that begins a new line.
+/// Environment bodies, items and groups spanning multiple lines add one level each.
+fn indent_level(token: &latex::SyntaxToken) -> usize {
+    let mut level = 0;
+    let mut child = latex::SyntaxElement::Token(token.clone());
+    for node in token.parent_ancestors() {
+        let is_delimiter = matches!(
+            child.kind(),
+            latex::L_CURLY | latex::R_CURLY | latex::L_BRACK | latex::R_BRACK
+        );
+
+        match node.kind() {
+            latex::ENVIRONMENT => {
+                if !matches!(child.kind(), latex::BEGIN | latex::END) && !is_document(&node) {
+                    level += 1;
+                }
+            }
+            latex::EQUATION => {
+                if !matches!(
+                    child.kind(),
+                    latex::BEGIN_EQUATION_NAME | latex::END_EQUATION_NAME
+                ) {
+                    level += 1;
+                }
+            }
+            latex::ENUM_ITEM => {
+                if child.kind() != latex::ENUM_ITEM_NAME {
+                    level += 1;
+                }
+            }
+            kind if is_group(kind) && !is_delimiter => {
+                level += 1;
+            }
+            _ => {}
+        };
+
+        child = NodeOrToken::Node(node);
+    }
+
+    level
+}
+
+fn is_document(node: &latex::SyntaxNode) -> bool {
+    latex::Environment::cast(node.clone())
+        .and_then(|environment| environment.begin())
+        .and_then(|begin| begin.name())
+        .and_then(|name| name.key())
+        .map_or(false, |name| name.to_string() == "document")
+}
+
+/// Spaces between a command and its arguments are ignored by TeX and can be dropped.
+fn is_removable(whitespace: &latex::SyntaxToken) -> bool {
+    let (parent, previous, next) = match (
+        whitespace.parent(),
+        whitespace.prev_sibling_or_token(),
+        whitespace.next_sibling_or_token(),
+    ) {
+        (Some(parent), Some(previous), Some(NodeOrToken::Node(next))) => (parent, previous, next),
+        _ => return false,
+    };
+
+    if !is_group(next.kind()) {
+        return false;
+    }
+
+    match previous {
+        NodeOrToken::Token(command) => {
+            command.kind().is_command_name()
+                && command
+                    .text()
+                    .ends_with(|c: char| c.is_ascii_alphabetic() || c == '@')
+                && (parent.kind() != latex::ENUM_ITEM || next.kind() == latex::BRACK_GROUP)
+        }
+        NodeOrToken::Node(group) => {
+            is_group(group.kind())
+                && matches!(
+                    parent.kind(),
+                    latex::BEGIN
+                        | latex::CITATION
+                        | latex::IMPORT
+                        | latex::LABEL_REFERENCE_RANGE
+                        | latex::COMMAND_DEFINITION
+                        | latex::DOCUMENT_COMMAND_DEFINITION
+                        | latex::MATH_OPERATOR
+                        | latex::ENVIRONMENT_DEFINITION
+                        | latex::THEOREM_DEFINITION
+                        | latex::COLOR_DEFINITION
+                        | latex::COLOR_SET_DEFINITION
+                        | latex::COLOR_LET_DEFINITION
+                        | latex::ACRONYM_DEFINITION
+                        | latex::ACRONYM_DECLARATION
+                        | latex::GLOSSARY_ENTRY_DEFINITION
+                )
+        }
+    }
+}
+
+/// Spaces between words of ordinary text can be replaced with a line break.
+fn is_breakable(whitespace: &latex::SyntaxToken) -> bool {
+    whitespace
+        .parent()
+        .map_or(false, |parent| parent.kind() == latex::TEXT)
+        && !whitespace
+            .parent_ancestors()
+            .any(|node| matches!(node.kind(), latex::FORMULA | latex::EQUATION | latex::KEY))
+}
@@ -1,3 +1,4 @@
+mod citation;
 mod label;
 
 use lsp_types::{InlayHint, InlayHintLabel, Range, Url};
@@ -20,6 +21,7 @@ pub fn find_all(db: &dyn Db, uri: &Url, range: Range) -> Option<Vec<InlayHint>>
 
     let range = line_index.offset_lsp_range(range);
     label::find_hints(db, document, range, &mut builder);
+    citation::find_hints(db, document, range, &mut builder);
     Some(builder.hints)
 }
 
@@ -0,0 +1,52 @@
+use rowan::{ast::AstNode, TextRange};
+
+use crate::{
+    citation,
+    db::{Document, Workspace},
+    syntax::{bibtex, latex},
+    Db,
+};
+
+use super::InlayHintBuilder;
+
+pub(super) fn find_hints(
+    db: &dyn Db,
+    document: Document,
+    range: TextRange,
+    builder: &mut InlayHintBuilder,
+) -> Option<()> {
+    let workspace = Workspace::get(db);
+    if !workspace.options(db).inlay_hints.citations {
+        return Some(());
+    }
+
+    let root = document.parse(db).as_tex()?.root(db);
+    let bib_roots: Vec<_> = workspace
+        .related(db, document)
+        .into_iter()
+        .filter_map(|child| child.parse(db).as_bib())
+        .filter_map(|data| bibtex::Root::cast(data.root(db)))
+        .collect();
+
+    for key in root
+        .descendants()
+        .filter_map(latex::Citation::cast)
+        .filter_map(|citation| citation.key_list())
+        .flat_map(|keys| keys.keys())
+    {
+        let key_range = latex::small_range(&key);
+        if key_range.intersect(range).is_none() {
+            continue;
+        }
+
+        let key = key.to_string();
+        if let Some(text) = bib_roots
+            .iter()
+            .find_map(|root| citation::render_short(&root.find_entry(&key)?))
+        {
+            builder.push(key_range.end(), text);
+        }
+    }
+
+    Some(())
+}
@@ -1,7 +1,7 @@
 use rowan::TextRange;
 
 use crate::{
-    db::{analysis::label, Document},
+    db::{analysis::label, Document, Workspace},
     util, Db,
 };
 
@@ -13,19 +13,59 @@ pub(super) fn find_hints(
     range: TextRange,
     builder: &mut InlayHintBuilder,
 ) -> Option<()> {
+    let workspace = Workspace::get(db);
+    let options = &workspace.options(db).inlay_hints;
     let data = document.parse(db).as_tex()?;
     for label in data
         .analyze(db)
         .labels(db)
         .iter()
         .copied()
-        .filter(|label| matches!(label.origin(db), label::Origin::Definition(_)))
         .filter(|label| label.range(db).intersect(range).is_some())
     {
-        if let Some(rendered) = util::label::render(db, document, label) {
-            builder.push(label.range(db).end(), rendered.reference(db));
-        }
+        match label.origin(db) {
+            label::Origin::Definition(_) if options.label_definitions => {
+                if let Some(rendered) = util::label::render(db, document, label) {
+                    builder.push(label.range(db).end(), rendered.reference(db));
+                }
+            }
+            label::Origin::Reference(_) | label::Origin::ReferenceRange(_)
+                if options.label_references =>
+            {
+                if let Some(text) = render_reference(db, workspace, document, label) {
+                    builder.push(label.range(db).end(), text);
+                }
+            }
+            _ => {}
+        };
     }
 
     Some(())
 }
+
+fn render_reference(
+    db: &dyn Db,
+    workspace: Workspace,
+    document: Document,
+    reference: label::Name,
+) -> Option<String> {
+    workspace
+        .related(db, document)
+        .into_iter()
+        .find_map(|child| {
+            let definition = child
+                .parse(db)
+                .as_tex()?
+                .analyze(db)
+                .labels(db)
+                .iter()
+                .copied()
+                .filter(|label| label.origin(db).as_definition().is_some())
+                .find(|label| label.name(db) == reference.name(db))?;
+
+            // Without a number from the `.aux` file, the hint would only repeat the kind.
+            util::label::render(db, child, definition)
+                .filter(|rendered| rendered.number.is_some())
+                .map(|rendered| rendered.short_reference(db))
+        })
+}
@@ -0,0 +1,58 @@
+use lsp_types::{LinkedEditingRanges, Position, Url};
+use rowan::{ast::AstNode, TextRange};
+
+use crate::{
+    syntax::latex::{self, HasCurly},
+    util::{cursor::CursorContext, line_index_ext::LineIndexExt},
+    Db,
+};
+
+pub fn find_all(db: &dyn Db, uri: &Url, position: Position) -> Option<LinkedEditingRanges> {
+    let context = CursorContext::new(db, uri, position, ())?;
+    let group = context
+        .cursor
+        .as_tex()?
+        .parent_ancestors()
+        .find_map(latex::CurlyGroupWord::cast)?;
+
+    if !context.is_inside_latex_curly(&group) {
+        return None;
+    }
+
+    let parent = group.syntax().parent()?;
+    if !matches!(parent.kind(), latex::BEGIN | latex::END) {
+        return None;
+    }
+
+    let environment = latex::Environment::cast(parent.parent()?)?;
+    let begin = environment.begin()?.name()?;
+    let end = environment.end()?.name()?;
+
+    // Both names have to be identical, otherwise editing would keep them mismatched.
+    let begin_name = begin.key().map(|key| key.to_string()).unwrap_or_default();
+    let end_name = end.key().map(|key| key.to_string()).unwrap_or_default();
+    if begin_name != end_name {
+        return None;
+    }
+
+    let ranges = vec![
+        context
+            .line_index
+            .line_col_lsp_range(find_name_range(&begin)?),
+        context
+            .line_index
+            .line_col_lsp_range(find_name_range(&end)?),
+    ];
+
+    Some(LinkedEditingRanges {
+        ranges,
+        word_pattern: None,
+    })
+}
+
+fn find_name_range(group: &latex::CurlyGroupWord) -> Option<TextRange> {
+    match group.key() {
+        Some(key) => Some(latex::small_range(&key)),
+        None => Some(TextRange::empty(group.left_curly()?.text_range().end())),
+    }
+}
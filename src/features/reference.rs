@@ -1,4 +1,6 @@
+mod command;
 mod entry;
+mod environment;
 mod label;
 mod string;
 
@@ -23,6 +25,8 @@ pub fn find_all(
     label::find_all_references(&context, &mut results);
     entry::find_all_references(&context, &mut results);
     string::find_all_references(&context, &mut results);
+    command::find_all_references(&context, &mut results);
+    environment::find_all_references(&context, &mut results);
 
     let locations = results
         .into_iter()
@@ -0,0 +1,51 @@
+use lsp_types::ReferenceContext;
+use rowan::ast::AstNode;
+
+use crate::{syntax::latex, util::cursor::CursorContext};
+
+use super::ReferenceResult;
+
+pub(super) fn find_all_references(
+    context: &CursorContext<&ReferenceContext>,
+    results: &mut Vec<ReferenceResult>,
+) -> Option<()> {
+    let db = context.db;
+    let name = context
+        .cursor
+        .as_tex()
+        .filter(|token| token.kind().is_command_name())?;
+
+    for document in context.related() {
+        if let Some(data) = document.parse(db).as_tex() {
+            let text = document.contents(db).text(db);
+            for range in data
+                .analyze(db)
+                .command_name_ranges(db)
+                .iter()
+                .copied()
+                .filter(|range| &text[std::ops::Range::<usize>::from(*range)] == name.text())
+            {
+                results.push(ReferenceResult { document, range });
+            }
+
+            if !context.params.include_declaration {
+                continue;
+            }
+
+            for command in data
+                .root(db)
+                .descendants()
+                .filter_map(latex::CommandDefinition::cast)
+                .filter_map(|definition| definition.name()?.command())
+                .filter(|command| command.text() == name.text())
+            {
+                results.push(ReferenceResult {
+                    document,
+                    range: command.text_range(),
+                });
+            }
+        }
+    }
+
+    Some(())
+}
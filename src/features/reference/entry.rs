@@ -1,13 +1,12 @@
 use lsp_types::ReferenceContext;
-use rowan::ast::AstNode;
 
 use crate::{
     db::parse::DocumentData,
-    syntax::{
-        bibtex::{self, HasName},
-        latex,
+    syntax::latex,
+    util::{
+        bibliography::{citation_keys, entry_keys},
+        cursor::CursorContext,
     },
-    util::cursor::CursorContext,
 };
 
 use super::ReferenceResult;
@@ -25,11 +24,7 @@ pub(super) fn find_all_references(
     for document in context.related() {
         match document.parse(db) {
             DocumentData::Tex(data) => {
-                data.root(db)
-                    .descendants()
-                    .filter_map(latex::Citation::cast)
-                    .filter_map(|citation| citation.key_list())
-                    .flat_map(|keys| keys.keys())
+                citation_keys(&data.root(db))
                     .filter(|key| key.to_string() == key_text)
                     .map(|key| latex::small_range(&key))
                     .for_each(|range| {
@@ -37,12 +32,9 @@ pub(super) fn find_all_references(
                     });
             }
             DocumentData::Bib(data) if context.params.include_declaration => {
-                data.root(db)
-                    .children()
-                    .filter_map(bibtex::Entry::cast)
-                    .filter_map(|entry| entry.name_token())
-                    .filter(|key| key.text() == key_text)
-                    .map(|key| key.text_range())
+                entry_keys(&data.root(db))
+                    .filter(|(_, key)| key.text() == key_text)
+                    .map(|(_, key)| key.text_range())
                     .for_each(|range| {
                         results.push(ReferenceResult { document, range });
                     });
@@ -50,7 +42,8 @@ pub(super) fn find_all_references(
             DocumentData::Bib(_)
             | DocumentData::Log(_)
             | DocumentData::TexlabRoot(_)
-            | DocumentData::Tectonic(_) => {}
+            | DocumentData::Tectonic(_)
+            | DocumentData::Latexmkrc(_) => {}
         };
     }
 
@@ -0,0 +1,48 @@
+use lsp_types::ReferenceContext;
+use rowan::ast::AstNode;
+
+use crate::{syntax::latex, util::cursor::CursorContext};
+
+use super::ReferenceResult;
+
+pub(super) fn find_all_references(
+    context: &CursorContext<&ReferenceContext>,
+    results: &mut Vec<ReferenceResult>,
+) -> Option<()> {
+    let db = context.db;
+    let (name_text, _) = context
+        .find_environment_name()
+        .or_else(|| context.find_environment_definition_name())
+        .filter(|(name, _)| !name.is_empty())?;
+
+    for document in context.related() {
+        if let Some(data) = document.parse(db).as_tex() {
+            for name in data
+                .root(db)
+                .descendants()
+                .filter_map(|node| {
+                    latex::Begin::cast(node.clone())
+                        .and_then(|begin| begin.name())
+                        .or_else(|| {
+                            if !context.params.include_declaration {
+                                return None;
+                            }
+
+                            latex::EnvironmentDefinition::cast(node.clone())
+                                .and_then(|definition| definition.name())
+                                .or_else(|| latex::TheoremDefinition::cast(node)?.name())
+                        })
+                })
+                .filter_map(|name| name.key())
+                .filter(|name| name.to_string() == name_text)
+            {
+                results.push(ReferenceResult {
+                    document,
+                    range: latex::small_range(&name),
+                });
+            }
+        }
+    }
+
+    Some(())
+}
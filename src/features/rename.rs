@@ -1,6 +1,7 @@
 mod command;
 mod entry;
 mod label;
+mod name;
 
 use lsp_types::{Position, Range, TextEdit, Url, WorkspaceEdit};
 use rowan::TextRange;
@@ -16,6 +17,9 @@ pub fn prepare_rename_all(db: &dyn Db, uri: &Url, position: Position) -> Option<
     let context = CursorContext::new(db, uri, position, ())?;
     let range = entry::prepare_rename(&context)
         .or_else(|| label::prepare_rename(&context))
+        .or_else(|| name::ENVIRONMENT.prepare_rename(&context))
+        .or_else(|| name::GLOSSARY_ENTRY.prepare_rename(&context))
+        .or_else(|| name::COLOR.prepare_rename(&context))
         .or_else(|| command::prepare_rename(&context))?;
 
     let line_index = context.document.contents(db).line_index(db);
@@ -31,6 +35,9 @@ pub fn rename_all(
     let context = CursorContext::new(db, uri, position, Params { new_name })?;
     let result = entry::rename(&context)
         .or_else(|| label::rename(&context))
+        .or_else(|| name::ENVIRONMENT.rename(&context))
+        .or_else(|| name::GLOSSARY_ENTRY.rename(&context))
+        .or_else(|| name::COLOR.rename(&context))
         .or_else(|| command::rename(&context))?;
 
     let changes = result
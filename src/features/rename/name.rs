@@ -0,0 +1,100 @@
+use rowan::{ast::AstNode, TextRange};
+use rustc_hash::FxHashMap;
+
+use crate::{syntax::latex, util::cursor::CursorContext};
+
+use super::{Indel, Params, RenameResult};
+
+/// A user-defined symbol whose definitions and references
+/// store the name inside a `CurlyGroupWord`.
+pub(super) struct NameKind {
+    definition: fn(latex::SyntaxNode) -> Option<latex::CurlyGroupWord>,
+    reference: fn(latex::SyntaxNode) -> Option<latex::CurlyGroupWord>,
+}
+
+pub(super) const ENVIRONMENT: NameKind = NameKind {
+    definition: |node| {
+        latex::EnvironmentDefinition::cast(node.clone())
+            .and_then(|definition| definition.name())
+            .or_else(|| latex::TheoremDefinition::cast(node)?.name())
+    },
+    reference: |node| {
+        latex::Begin::cast(node.clone())
+            .and_then(|begin| begin.name())
+            .or_else(|| latex::End::cast(node)?.name())
+    },
+};
+
+pub(super) const GLOSSARY_ENTRY: NameKind = NameKind {
+    definition: |node| {
+        latex::GlossaryEntryDefinition::cast(node.clone())
+            .and_then(|definition| definition.name())
+            .or_else(|| latex::AcronymDefinition::cast(node.clone())?.name())
+            .or_else(|| latex::AcronymDeclaration::cast(node)?.name())
+    },
+    reference: |node| {
+        latex::GlossaryEntryReference::cast(node.clone())
+            .and_then(|reference| reference.name())
+            .or_else(|| latex::AcronymReference::cast(node)?.name())
+    },
+};
+
+pub(super) const COLOR: NameKind = NameKind {
+    definition: |node| {
+        latex::ColorDefinition::cast(node.clone())
+            .and_then(|definition| definition.name())
+            .or_else(|| latex::ColorLetDefinition::cast(node)?.name())
+    },
+    reference: |node| latex::ColorReference::cast(node)?.name(),
+};
+
+impl NameKind {
+    pub(super) fn prepare_rename<T>(&self, context: &CursorContext<T>) -> Option<TextRange> {
+        let (name_text, range) = self.find_name(context)?;
+
+        // Predefined names like the color `red` or the `itemize` environment cannot be renamed.
+        let is_defined = context
+            .related()
+            .filter_map(|document| document.parse(context.db).as_tex())
+            .flat_map(|data| data.root(context.db).descendants())
+            .filter_map(self.definition)
+            .filter_map(|name| name.key())
+            .any(|name| name.to_string() == name_text);
+
+        is_defined.then_some(range)
+    }
+
+    pub(super) fn rename(&self, context: &CursorContext<Params>) -> Option<RenameResult> {
+        self.prepare_rename(context)?;
+        let (name_text, _) = self.find_name(context)?;
+
+        let mut changes = FxHashMap::default();
+        for document in context.related() {
+            if let Some(data) = document.parse(context.db).as_tex() {
+                let edits = data
+                    .root(context.db)
+                    .descendants()
+                    .filter_map(|node| {
+                        (self.definition)(node.clone()).or_else(|| (self.reference)(node))
+                    })
+                    .filter_map(|name| name.key())
+                    .filter(|name| name.to_string() == name_text)
+                    .map(|name| Indel {
+                        delete: latex::small_range(&name),
+                        insert: context.params.new_name.clone(),
+                    })
+                    .collect();
+
+                changes.insert(document, edits);
+            }
+        }
+
+        Some(RenameResult { changes })
+    }
+
+    fn find_name<T>(&self, context: &CursorContext<T>) -> Option<(String, TextRange)> {
+        context
+            .find_name_of(|node| (self.definition)(node.clone()).or_else(|| (self.reference)(node)))
+            .filter(|(name, _)| !name.is_empty())
+    }
+}
@@ -0,0 +1,164 @@
+use lsp_types::{Position, Range, SelectionRange, Url};
+use rowan::{ast::AstNode, TextRange, TextSize};
+
+use crate::{
+    db::{parse::DocumentData, Workspace},
+    syntax::{bibtex, latex},
+    util::{line_index::LineIndex, line_index_ext::LineIndexExt},
+    Db,
+};
+
+pub fn find_all(db: &dyn Db, uri: &Url, positions: Vec<Position>) -> Option<Vec<SelectionRange>> {
+    let document = Workspace::get(db).lookup_uri(db, uri)?;
+    let line_index = document.contents(db).line_index(db);
+    let data = document.parse(db);
+
+    let results = positions
+        .into_iter()
+        .map(|position| {
+            let offset = line_index.offset_lsp(position);
+            let ranges = match data {
+                DocumentData::Tex(data) => find_latex_ranges(&data.root(db), offset),
+                DocumentData::Bib(data) => find_bibtex_ranges(&data.root(db), offset),
+                DocumentData::Log(_)
+                | DocumentData::TexlabRoot(_)
+                | DocumentData::Tectonic(_)
+                | DocumentData::Latexmkrc(_) => Vec::new(),
+            };
+
+            build_selection_range(line_index, position, ranges)
+        })
+        .collect();
+
+    Some(results)
+}
+
+fn find_latex_ranges(root: &latex::SyntaxNode, offset: TextSize) -> Vec<TextRange> {
+    let mut builder = RangeBuilder::default();
+    let token = match root
+        .token_at_offset(offset)
+        .find(|token| !is_latex_trivia(token.kind()))
+    {
+        Some(token) => token,
+        None => return Vec::new(),
+    };
+
+    builder.push(token.text_range());
+    for node in token.parent_ancestors() {
+        if let Some(environment) = latex::Environment::cast(node.clone()) {
+            if let Some(body) = find_environment_body(&environment) {
+                builder.push(body);
+            }
+        }
+
+        builder.push(trimmed_range(&node, is_latex_trivia));
+    }
+
+    builder.ranges
+}
+
+/// Finds the range between `\begin{...}` and `\end{...}` without surrounding whitespace.
+fn find_environment_body(environment: &latex::Environment) -> Option<TextRange> {
+    let mut children = environment
+        .syntax()
+        .children()
+        .filter(|node| !matches!(node.kind(), latex::BEGIN | latex::END));
+
+    let first = children.next()?;
+    let last = children.last().unwrap_or_else(|| first.clone());
+    Some(TextRange::new(
+        first.text_range().start(),
+        trimmed_range(&last, is_latex_trivia).end(),
+    ))
+}
+
+fn find_bibtex_ranges(root: &bibtex::SyntaxNode, offset: TextSize) -> Vec<TextRange> {
+    let mut builder = RangeBuilder::default();
+    let token = match root
+        .token_at_offset(offset)
+        .find(|token| !is_bibtex_trivia(token.kind()))
+    {
+        Some(token) => token,
+        None => return Vec::new(),
+    };
+
+    builder.push(token.text_range());
+    for node in token.parent_ancestors() {
+        builder.push(trimmed_range(&node, is_bibtex_trivia));
+    }
+
+    builder.ranges
+}
+
+fn is_latex_trivia(kind: latex::SyntaxKind) -> bool {
+    matches!(kind, latex::WHITESPACE | latex::LINE_BREAK | latex::COMMENT)
+}
+
+fn is_bibtex_trivia(kind: bibtex::SyntaxKind) -> bool {
+    matches!(kind, bibtex::WHITESPACE | bibtex::JUNK)
+}
+
+/// Computes the range of a node without leading and trailing trivia.
+fn trimmed_range<L: rowan::Language>(
+    node: &rowan::SyntaxNode<L>,
+    is_trivia: impl Fn(L::Kind) -> bool,
+) -> TextRange {
+    let full_range = node.text_range();
+
+    let mut first = node.first_token();
+    while let Some(token) = first
+        .clone()
+        .filter(|token| is_trivia(token.kind()) && token.text_range().end() < full_range.end())
+    {
+        first = token.next_token();
+    }
+
+    let mut last = node.last_token();
+    while let Some(token) = last
+        .clone()
+        .filter(|token| is_trivia(token.kind()) && token.text_range().start() > full_range.start())
+    {
+        last = token.prev_token();
+    }
+
+    match (first, last) {
+        (Some(first), Some(last)) if first.text_range().start() <= last.text_range().end() => {
+            TextRange::new(first.text_range().start(), last.text_range().end())
+        }
+        _ => full_range,
+    }
+}
+
+#[derive(Default)]
+struct RangeBuilder {
+    ranges: Vec<TextRange>,
+}
+
+impl RangeBuilder {
+    /// Adds a range if it strictly contains the previous one.
+    fn push(&mut self, range: TextRange) {
+        match self.ranges.last() {
+            Some(last) if *last == range || !range.contains_range(*last) => {}
+            _ => self.ranges.push(range),
+        };
+    }
+}
+
+fn build_selection_range(
+    line_index: &LineIndex,
+    position: Position,
+    ranges: Vec<TextRange>,
+) -> SelectionRange {
+    let mut result: Option<SelectionRange> = None;
+    for range in ranges.into_iter().rev() {
+        result = Some(SelectionRange {
+            range: line_index.line_col_lsp_range(range),
+            parent: result.map(Box::new),
+        });
+    }
+
+    result.unwrap_or_else(|| SelectionRange {
+        range: Range::new(position, position),
+        parent: None,
+    })
+}
@@ -0,0 +1,146 @@
+mod bibtex;
+mod latex;
+
+use lsp_types::{
+    Position, Range, SemanticToken, SemanticTokenType, SemanticTokens, SemanticTokensLegend, Url,
+};
+use rowan::{TextLen, TextRange};
+
+use crate::{
+    db::{parse::DocumentData, Workspace},
+    util::{line_index::LineIndex, line_index_ext::LineIndexExt},
+    Db,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[repr(u32)]
+enum TokenKind {
+    LabelDefinition,
+    LabelReference,
+    CitationKey,
+    SectionCommand,
+    Math,
+    Comment,
+    Verbatim,
+    EntryType,
+    EntryKey,
+    FieldName,
+    StringDefinition,
+    StringReference,
+}
+
+impl TokenKind {
+    const ALL: [TokenKind; 12] = [
+        Self::LabelDefinition,
+        Self::LabelReference,
+        Self::CitationKey,
+        Self::SectionCommand,
+        Self::Math,
+        Self::Comment,
+        Self::Verbatim,
+        Self::EntryType,
+        Self::EntryKey,
+        Self::FieldName,
+        Self::StringDefinition,
+        Self::StringReference,
+    ];
+
+    fn token_type(self) -> SemanticTokenType {
+        match self {
+            Self::LabelDefinition => SemanticTokenType::VARIABLE,
+            Self::LabelReference => SemanticTokenType::PARAMETER,
+            Self::CitationKey => SemanticTokenType::ENUM_MEMBER,
+            Self::SectionCommand => SemanticTokenType::NAMESPACE,
+            Self::Math => SemanticTokenType::NUMBER,
+            Self::Comment => SemanticTokenType::COMMENT,
+            Self::Verbatim => SemanticTokenType::STRING,
+            Self::EntryType => SemanticTokenType::TYPE,
+            Self::EntryKey => SemanticTokenType::ENUM,
+            Self::FieldName => SemanticTokenType::PROPERTY,
+            Self::StringDefinition => SemanticTokenType::FUNCTION,
+            Self::StringReference => SemanticTokenType::MACRO,
+        }
+    }
+}
+
+pub fn legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TokenKind::ALL
+            .into_iter()
+            .map(TokenKind::token_type)
+            .collect(),
+        token_modifiers: Vec::new(),
+    }
+}
+
+pub fn find_all(db: &dyn Db, uri: &Url, viewport: Option<Range>) -> Option<SemanticTokens> {
+    let document = Workspace::get(db).lookup_uri(db, uri)?;
+    let text = document.contents(db).text(db);
+    let line_index = document.contents(db).line_index(db);
+    let range = viewport.map_or_else(
+        || TextRange::up_to(text.text_len()),
+        |viewport| line_index.offset_lsp_range(viewport),
+    );
+
+    let mut builder = TokenBuilder {
+        text,
+        line_index,
+        last_position: Position::new(0, 0),
+        tokens: Vec::new(),
+    };
+
+    match document.parse(db) {
+        DocumentData::Tex(data) => latex::find_tokens(&data.root(db), range, &mut builder),
+        DocumentData::Bib(data) => bibtex::find_tokens(&data.root(db), range, &mut builder),
+        DocumentData::Log(_)
+        | DocumentData::TexlabRoot(_)
+        | DocumentData::Tectonic(_)
+        | DocumentData::Latexmkrc(_) => {
+            return None;
+        }
+    };
+
+    Some(SemanticTokens {
+        result_id: None,
+        data: builder.tokens,
+    })
+}
+
+struct TokenBuilder<'db> {
+    text: &'db str,
+    line_index: &'db LineIndex,
+    last_position: Position,
+    tokens: Vec<SemanticToken>,
+}
+
+impl<'db> TokenBuilder<'db> {
+    /// Adds a token to the result. The tokens have to be pushed in document order.
+    /// Tokens spanning multiple lines are split up since not every client supports
+    /// multiline tokens.
+    pub fn push(&mut self, range: TextRange, kind: TokenKind) {
+        for line_range in self.line_index.lines(range) {
+            let text = self.text[line_range].trim_end_matches(['\r', '\n']);
+            if text.is_empty() {
+                continue;
+            }
+
+            let position = self.line_index.line_col_lsp(line_range.start());
+            let delta_line = position.line - self.last_position.line;
+            let delta_start = if delta_line == 0 {
+                position.character - self.last_position.character
+            } else {
+                position.character
+            };
+
+            self.tokens.push(SemanticToken {
+                delta_line,
+                delta_start,
+                length: text.encode_utf16().count() as u32,
+                token_type: kind as u32,
+                token_modifiers_bitset: 0,
+            });
+
+            self.last_position = position;
+        }
+    }
+}
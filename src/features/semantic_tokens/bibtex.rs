@@ -0,0 +1,31 @@
+use rowan::TextRange;
+
+use crate::syntax::bibtex;
+
+use super::{TokenBuilder, TokenKind};
+
+pub(super) fn find_tokens(root: &bibtex::SyntaxNode, range: TextRange, builder: &mut TokenBuilder) {
+    for token in root
+        .descendants_with_tokens()
+        .filter_map(|element| element.into_token())
+        .filter(|token| token.text_range().intersect(range).is_some())
+    {
+        let parent = match token.parent() {
+            Some(parent) => parent.kind(),
+            None => continue,
+        };
+
+        let kind = match (token.kind(), parent) {
+            (bibtex::TYPE, bibtex::PREAMBLE | bibtex::STRING | bibtex::ENTRY) => {
+                TokenKind::EntryType
+            }
+            (bibtex::NAME, bibtex::ENTRY) => TokenKind::EntryKey,
+            (bibtex::NAME, bibtex::FIELD) => TokenKind::FieldName,
+            (bibtex::NAME, bibtex::STRING) => TokenKind::StringDefinition,
+            (bibtex::NAME, bibtex::LITERAL) => TokenKind::StringReference,
+            _ => continue,
+        };
+
+        builder.push(token.text_range(), kind);
+    }
+}
@@ -0,0 +1,73 @@
+use rowan::{ast::AstNode, NodeOrToken, TextRange, WalkEvent};
+
+use crate::{syntax::latex, util::lang_data::LANGUAGE_DATA};
+
+use super::{TokenBuilder, TokenKind};
+
+pub(super) fn find_tokens(root: &latex::SyntaxNode, range: TextRange, builder: &mut TokenBuilder) {
+    let mut stack: Vec<Option<TokenKind>> = Vec::new();
+    for event in root.preorder_with_tokens() {
+        match event {
+            WalkEvent::Enter(NodeOrToken::Node(node)) => {
+                let kind = classify_node(&node).or_else(|| stack.last().copied().flatten());
+                stack.push(kind);
+            }
+            WalkEvent::Leave(NodeOrToken::Node(_)) => {
+                stack.pop();
+            }
+            WalkEvent::Enter(NodeOrToken::Token(token)) => {
+                if token.text_range().intersect(range).is_none() {
+                    continue;
+                }
+
+                let inherited = stack.last().copied().flatten();
+                let kind = match token.kind() {
+                    latex::WHITESPACE | latex::LINE_BREAK | latex::MISSING => None,
+                    latex::COMMENT => Some(TokenKind::Comment),
+                    latex::VERBATIM => inherited.or(Some(TokenKind::Verbatim)),
+                    latex::PART_NAME
+                    | latex::CHAPTER_NAME
+                    | latex::SECTION_NAME
+                    | latex::SUBSECTION_NAME
+                    | latex::SUBSUBSECTION_NAME
+                    | latex::PARAGRAPH_NAME
+                    | latex::SUBPARAGRAPH_NAME => Some(TokenKind::SectionCommand),
+                    _ => inherited,
+                };
+
+                if let Some(kind) = kind {
+                    builder.push(token.text_range(), kind);
+                }
+            }
+            WalkEvent::Leave(NodeOrToken::Token(_)) => {}
+        };
+    }
+}
+
+fn classify_node(node: &latex::SyntaxNode) -> Option<TokenKind> {
+    match node.kind() {
+        latex::KEY => match node.parent()?.parent()?.kind() {
+            latex::LABEL_DEFINITION => Some(TokenKind::LabelDefinition),
+            latex::LABEL_REFERENCE | latex::LABEL_REFERENCE_RANGE => {
+                Some(TokenKind::LabelReference)
+            }
+            latex::CITATION => Some(TokenKind::CitationKey),
+            _ => None,
+        },
+        latex::FORMULA | latex::EQUATION => Some(TokenKind::Math),
+        latex::ENVIRONMENT => {
+            let name = latex::Environment::cast(node.clone())?
+                .begin()?
+                .name()?
+                .key()?
+                .to_string();
+
+            LANGUAGE_DATA
+                .math_environments
+                .contains(&name)
+                .then_some(TokenKind::Math)
+        }
+        latex::BLOCK_COMMENT => Some(TokenKind::Comment),
+        _ => None,
+    }
+}
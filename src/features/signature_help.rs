@@ -0,0 +1,190 @@
+use lsp_types::{
+    Documentation, ParameterInformation, ParameterLabel, Position, SignatureHelp,
+    SignatureInformation, Url,
+};
+use rowan::{ast::AstNode, TextSize};
+
+use crate::{
+    syntax::latex::{self, HasCurly},
+    util::{
+        command_signature::{CommandArgument, CommandSignature},
+        components::COMPONENT_DATABASE,
+        cursor::CursorContext,
+    },
+    Db,
+};
+
+pub fn find_all(db: &dyn Db, uri: &Url, position: Position) -> Option<SignatureHelp> {
+    let context = CursorContext::new(db, uri, position, ())?;
+    let root = context.document.parse(db).as_tex()?.root(db);
+    let token = root.token_at_offset(context.offset).left_biased()?;
+
+    // Nested commands without a known signature should not hide the signature of the outer command.
+    token
+        .parent_ancestors()
+        .filter_map(latex::GenericCommand::cast)
+        .find_map(|command| find_signatures(&context, &command))
+}
+
+fn find_signatures(
+    context: &CursorContext,
+    command: &latex::GenericCommand,
+) -> Option<SignatureHelp> {
+    let name = command.name()?;
+    if context.offset < name.text_range().end() {
+        return None;
+    }
+
+    let (groups, active_group) = find_groups(command, context.offset);
+
+    let mut signatures = Vec::new();
+    for document in context.related() {
+        if let Some(data) = document.parse(context.db).as_tex() {
+            for signature in CommandSignature::find_all(&data.root(context.db), name.text()) {
+                let documentation: Vec<_> = signature
+                    .arguments
+                    .iter()
+                    .map(|argument| {
+                        let default = argument.default.as_ref()?;
+                        Some(format!("Default: {default}"))
+                    })
+                    .collect();
+
+                signatures.push((signature, documentation));
+            }
+        }
+    }
+
+    let component_name = &name.text()[1..];
+    for component in COMPONENT_DATABASE.linked_components(context.db, context.document) {
+        for component_command in component
+            .commands
+            .iter()
+            .filter(|command| command.name == component_name && !command.parameters.is_empty())
+        {
+            let signature = CommandSignature {
+                name: name.text().into(),
+                arguments: component_command
+                    .parameters
+                    .iter()
+                    .map(|_| CommandArgument::mandatory())
+                    .collect(),
+            };
+
+            let documentation: Vec<_> = component_command
+                .parameters
+                .iter()
+                .map(|param| {
+                    let values: Vec<_> = param.0.iter().map(|arg| arg.name.as_str()).collect();
+                    (!values.is_empty()).then(|| format!("Possible values: {}", values.join(", ")))
+                })
+                .collect();
+
+            signatures.push((signature, documentation));
+        }
+    }
+
+    let signatures: Vec<_> = signatures
+        .into_iter()
+        .filter(|(signature, _)| !signature.arguments.is_empty())
+        .map(|(signature, documentation)| {
+            let active_parameter = find_active_parameter(&signature, &groups, active_group);
+            build_signature(&signature, documentation, active_parameter)
+        })
+        .collect();
+
+    let active_parameter = signatures.first()?.active_parameter;
+    Some(SignatureHelp {
+        signatures,
+        active_signature: Some(0),
+        active_parameter,
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ActiveGroup {
+    Inside(usize),
+    After(usize),
+}
+
+/// Collects the opening delimiters of the groups of a command call
+/// and determines where the cursor is located relative to them.
+fn find_groups(command: &latex::GenericCommand, offset: TextSize) -> (Vec<char>, ActiveGroup) {
+    let mut groups = Vec::new();
+    let mut active_group = None;
+    let mut groups_before = 0;
+    for node in command.syntax().children() {
+        let (open, close) = if let Some(group) = latex::CurlyGroup::cast(node.clone()) {
+            ('{', group.right_curly())
+        } else if let Some(group) = latex::MixedGroup::cast(node.clone()) {
+            let open = group
+                .left_delim()
+                .and_then(|delim| delim.text().chars().next())
+                .unwrap_or('[');
+
+            (open, group.right_delim())
+        } else {
+            continue;
+        };
+
+        let index = groups.len();
+        groups.push(open);
+
+        let start = node.text_range().start();
+        match close {
+            Some(close) if close.text_range().end() <= offset => groups_before = index + 1,
+            Some(close) if start < offset && offset <= close.text_range().start() => {
+                active_group = Some(ActiveGroup::Inside(index));
+            }
+            None if start < offset => active_group = Some(ActiveGroup::Inside(index)),
+            _ => {}
+        };
+    }
+
+    let active_group = active_group.unwrap_or(ActiveGroup::After(groups_before));
+    (groups, active_group)
+}
+
+fn find_active_parameter(
+    signature: &CommandSignature,
+    groups: &[char],
+    active_group: ActiveGroup,
+) -> Option<u32> {
+    let slots = signature.match_groups(groups.iter().copied());
+    let index = match active_group {
+        ActiveGroup::Inside(index) => slots[index]?,
+        ActiveGroup::After(0) => 0,
+        ActiveGroup::After(count) => slots[count - 1]? + 1,
+    };
+
+    (index < signature.arguments.len()).then_some(index as u32)
+}
+
+fn build_signature(
+    signature: &CommandSignature,
+    documentation: Vec<Option<String>>,
+    active_parameter: Option<u32>,
+) -> SignatureInformation {
+    let mut label = signature.name.clone();
+    let mut parameters = Vec::new();
+    for (i, (argument, documentation)) in signature.arguments.iter().zip(documentation).enumerate()
+    {
+        label.push(argument.open);
+        let start = label.encode_utf16().count() as u32;
+        label.push_str(&format!("#{}", i + 1));
+        let end = label.encode_utf16().count() as u32;
+        label.push(argument.close);
+
+        parameters.push(ParameterInformation {
+            label: ParameterLabel::LabelOffsets([start, end]),
+            documentation: documentation.map(Documentation::String),
+        });
+    }
+
+    SignatureInformation {
+        label,
+        documentation: None,
+        parameters: Some(parameters),
+        active_parameter,
+    }
+}
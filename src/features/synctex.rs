@@ -0,0 +1,148 @@
+use std::{
+    fs::{self, File},
+    io::Read,
+    path::{Component, Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use flate2::read::GzDecoder;
+use lsp_types::{Location, Position, Range, TextDocumentIdentifier, Url};
+use once_cell::sync::Lazy;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    db::{Document, Workspace},
+    parser::parse_synctex,
+    syntax::synctex::SyncTex,
+    Db,
+};
+
+/// Parsed SyncTeX files together with the modification time of the file they were read from.
+static CACHE: Lazy<Mutex<FxHashMap<PathBuf, (SystemTime, Arc<SyncTex>)>>> =
+    Lazy::new(Default::default);
+
+#[derive(Debug, PartialEq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForwardSearchPosition {
+    pub page: u32,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InverseSearchParams {
+    pub text_document: TextDocumentIdentifier,
+    pub page: u32,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Maps a position of a TeX document to a box of the PDF (in points from the top left corner).
+pub fn find_position(db: &dyn Db, uri: &Url, position: Position) -> Option<ForwardSearchPosition> {
+    let child = Workspace::get(db).lookup_uri(db, uri)?;
+    let (synctex, working_dir) = load(db, child)?;
+
+    let tex_path = normalize(child.location(db).path(db).as_deref()?);
+    let tag = synctex
+        .inputs
+        .iter()
+        .find(|(_, path)| normalize(&working_dir.join(path)) == tex_path)
+        .map(|(tag, _)| *tag)?;
+
+    let area = synctex.forward(tag, position.line + 1)?;
+    Some(ForwardSearchPosition {
+        page: area.page,
+        x: area.x,
+        y: area.y,
+        width: area.width,
+        height: area.height,
+    })
+}
+
+/// Maps a point of a PDF page back to the line of the TeX document which produced it.
+pub fn find_source(db: &dyn Db, params: &InverseSearchParams) -> Option<Location> {
+    let child = Workspace::get(db).lookup_uri(db, &params.text_document.uri)?;
+    let (synctex, working_dir) = load(db, child)?;
+
+    let node = synctex.inverse(params.page, params.x, params.y)?;
+    let path = normalize(&working_dir.join(synctex.inputs.get(&node.tag)?));
+    let uri = Url::from_file_path(path).ok()?;
+    let position = Position::new(node.line - 1, 0);
+    Some(Location::new(uri, Range::new(position, position)))
+}
+
+/// Reads the SyncTeX file of the root document and returns it together with the directory
+/// which relative input paths refer to.
+fn load(db: &dyn Db, child: Document) -> Option<(Arc<SyncTex>, PathBuf)> {
+    let workspace = Workspace::get(db);
+    let parent = workspace
+        .parents(db, child)
+        .iter()
+        .copied()
+        .next()
+        .unwrap_or(child);
+
+    let working_dir = workspace.working_dir(db, parent.directory(db));
+    let stem = parent.location(db).stem(db)?;
+
+    // `latexmk` may keep the SyncTeX file next to the other auxiliary files.
+    let synctex = [
+        workspace.output_dir(db, working_dir),
+        workspace.aux_dir(db, working_dir),
+    ]
+    .into_iter()
+    .filter_map(|dir| dir.path(db).as_deref())
+    .find_map(|dir| {
+        read_cached(&dir.join(format!("{stem}.synctex.gz")))
+            .or_else(|| read_cached(&dir.join(format!("{stem}.synctex"))))
+    })?;
+
+    Some((synctex, working_dir.path(db).clone()?))
+}
+
+/// Parses the given SyncTeX file unless it has not changed since the last request.
+fn read_cached(path: &Path) -> Option<Arc<SyncTex>> {
+    let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok()?;
+    let mut cache = CACHE.lock().unwrap();
+    if let Some((_, synctex)) = cache.get(path).filter(|(time, _)| *time == modified) {
+        return Some(Arc::clone(synctex));
+    }
+
+    let text = if path.extension().map_or(false, |ext| ext == "gz") {
+        read_compressed(path)?
+    } else {
+        fs::read_to_string(path).ok()?
+    };
+
+    let synctex = Arc::new(parse_synctex(&text));
+    cache.insert(path.to_path_buf(), (modified, Arc::clone(&synctex)));
+    Some(synctex)
+}
+
+fn read_compressed(path: &Path) -> Option<String> {
+    let mut decoder = GzDecoder::new(File::open(path).ok()?);
+    let mut text = String::new();
+    decoder.read_to_string(&mut text).ok()?;
+    Some(text)
+}
+
+/// Removes `.` and `..` components without accessing the file system.
+fn normalize(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                result.pop();
+            }
+            _ => result.push(component),
+        };
+    }
+
+    result
+}
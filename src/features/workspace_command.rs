@@ -61,6 +61,12 @@ impl CleanCommand {
             .as_deref()
             .ok_or_else(|| CleanError::NoLocalFile(uri.clone()))?;
 
+        let aux_dir = workspace
+            .aux_dir(db, working_dir)
+            .path(db)
+            .as_deref()
+            .ok_or_else(|| CleanError::NoLocalFile(uri.clone()))?;
+
         let path = document
             .location(db)
             .path(db)
@@ -75,6 +81,7 @@ impl CleanCommand {
         let executable = "latexmk".to_string();
         let args = vec![
             format!("-outdir={}", output_dir.display()),
+            format!("-auxdir={}", aux_dir.display()),
             flag.to_string(),
             path.display().to_string(),
         ];
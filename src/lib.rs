@@ -29,6 +29,7 @@ pub struct Jar(
     db::parse::TexDocumentData_analyze,
     db::parse::BibDocumentData,
     db::parse::LogDocumentData,
+    db::parse::LatexmkrcData,
     db::analysis::TexLink,
     db::analysis::label::Number,
     db::analysis::label::Name,
@@ -39,14 +40,20 @@ pub struct Jar(
     db::hidden_dependency,
     db::source_dependency,
     db::dependency_graph,
+    db::magic_root,
     db::Workspace,
     db::Workspace_working_dir,
     db::Workspace_output_dir,
+    db::Workspace_aux_dir,
+    db::Workspace_latexmkrc,
     db::Workspace_parents,
     db::Workspace_related,
     db::Workspace_number_of_label,
     db::diagnostics::tex::collect,
     db::diagnostics::bib::collect,
+    db::diagnostics::citations::collect,
+    db::diagnostics::entries::collect,
+    db::diagnostics::label::collect,
     db::diagnostics::log::collect,
     db::diagnostics::collect,
     db::diagnostics::collect_filtered,
@@ -10,6 +10,7 @@ pub struct Options {
     pub root_directory: Option<PathBuf>,
     pub aux_directory: Option<PathBuf>,
     pub bibtex_formatter: BibtexFormatter,
+    pub bibtex_dialect: BibtexDialect,
     pub latex_formatter: LatexFormatter,
     pub formatter_line_length: Option<i32>,
     pub diagnostics: DiagnosticsOptions,
@@ -18,6 +19,7 @@ pub struct Options {
     pub chktex: ChktexOptions,
     pub latexindent: LatexindentOptions,
     pub forward_search: ForwardSearchOptions,
+    pub inlay_hints: InlayHintOptions,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
@@ -42,6 +44,19 @@ impl Default for BibtexFormatter {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BibtexDialect {
+    Bibtex,
+    Biblatex,
+}
+
+impl Default for BibtexDialect {
+    fn default() -> Self {
+        Self::Biblatex
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum LatexFormatter {
@@ -71,6 +86,25 @@ pub struct BuildOptions {
     pub args: BuildArgs,
     pub on_save: bool,
     pub forward_search_after: bool,
+    pub recipes: Vec<BuildRecipe>,
+    pub default_recipe: Option<String>,
+}
+
+/// A named sequence of build steps like `pdflatex`, `biber`, `pdflatex`.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct BuildRecipe {
+    pub name: String,
+    pub steps: Vec<BuildStep>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct BuildStep {
+    pub executable: String,
+    pub args: Vec<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -114,12 +148,35 @@ pub struct ForwardSearchOptions {
     pub args: Option<Vec<String>>,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct InlayHintOptions {
+    pub label_definitions: bool,
+    pub label_references: bool,
+    pub citations: bool,
+}
+
+impl Default for InlayHintOptions {
+    fn default() -> Self {
+        Self {
+            label_definitions: true,
+            label_references: true,
+            citations: true,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[serde(default)]
 pub struct DiagnosticsOptions {
     pub allowed_patterns: Vec<DiagnosticsPattern>,
     pub ignored_patterns: Vec<DiagnosticsPattern>,
+    pub unused_labels: bool,
+    pub unused_entries: bool,
+    pub likely_duplicate_entries: bool,
+    pub unknown_fields: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
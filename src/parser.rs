@@ -1,5 +1,15 @@
 mod bibtex;
+mod blg;
 mod build_log;
 mod latex;
+mod latexmkrc;
+mod synctex;
 
-pub use self::{bibtex::parse_bibtex, build_log::parse_build_log, latex::parse_latex};
+pub use self::{
+    bibtex::parse_bibtex,
+    blg::parse_blg,
+    build_log::{parse_build_log, BuildLogStream},
+    latex::parse_latex,
+    latexmkrc::parse_latexmkrc,
+    synctex::parse_synctex,
+};
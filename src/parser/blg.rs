@@ -0,0 +1,108 @@
+use std::path::{Path, PathBuf};
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+use crate::syntax::{BuildError, BuildErrorKind, BuildErrorLevel, BuildLog};
+
+static BIBTEX_ERROR_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new("(?m)^(?P<msg>[^\r\n]*?)---line (?P<line>\\d+) of file (?P<file>[^\r\n]+?)\r?$")
+        .unwrap()
+});
+
+static BIBTEX_WARNING_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        "(?m)^Warning--(?P<msg>[^\r\n]*)\r?\n--line (?P<line>\\d+) of file (?P<file>[^\r\n]+?)\r?$",
+    )
+    .unwrap()
+});
+
+static BIBER_MESSAGE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new("(?m)^\\[\\d+\\] [^\r\n]*?> (?P<level>ERROR|WARN) - BibTeX subsystem: (?P<file>[^\r\n]+?), line (?P<line>\\d+), (?P<msg>[^\r\n]*?)\r?$").unwrap()
+});
+
+static BIBER_SOURCE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new("Looking for [a-z]+ (format )?file '(?P<file>[^']+)'").unwrap());
+
+static BIBER_TEMP_FILE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new("^(?P<name>.+?)_\\d+\\.utf8$").unwrap());
+
+/// Parses the log of BibTeX or biber (`.blg`). The paths of the errors refer to `.bib` files.
+pub fn parse_blg(log: &str) -> BuildLog {
+    let mut errors = Vec::new();
+    for captures in BIBTEX_ERROR_REGEX.captures_iter(log) {
+        let path = PathBuf::from(&captures["file"]);
+        errors.push(create_error(&captures, BuildErrorLevel::Error, path));
+    }
+
+    for captures in BIBTEX_WARNING_REGEX.captures_iter(log) {
+        let path = PathBuf::from(&captures["file"]);
+        errors.push(create_error(&captures, BuildErrorLevel::Warning, path));
+    }
+
+    let sources: Vec<&str> = BIBER_SOURCE_REGEX
+        .captures_iter(log)
+        .filter_map(|captures| Some(captures.name("file")?.as_str()))
+        .collect();
+
+    for captures in BIBER_MESSAGE_REGEX.captures_iter(log) {
+        let level = match &captures["level"] {
+            "ERROR" => BuildErrorLevel::Error,
+            _ => BuildErrorLevel::Warning,
+        };
+
+        let path = PathBuf::from(find_biber_source(&captures["file"], &sources));
+        errors.push(create_error(&captures, level, path));
+    }
+
+    BuildLog { errors }
+}
+
+fn create_error(captures: &Captures, level: BuildErrorLevel, relative_path: PathBuf) -> BuildError {
+    let kind = match level {
+        BuildErrorLevel::Error => BuildErrorKind::Error,
+        BuildErrorLevel::Warning => BuildErrorKind::Warning,
+    };
+
+    BuildError {
+        relative_path,
+        level,
+        kind,
+        message: captures["msg"].to_string(),
+        hint: None,
+        line: captures["line"]
+            .parse::<u32>()
+            .ok()
+            .and_then(|line| line.checked_sub(1)),
+    }
+}
+
+/// Biber reports errors in a temporary copy of the data source (e.g. `/tmp/biber_tmp_XYZ/refs.bib_1234.utf8`),
+/// which is mapped back to the data source as it was given by the document.
+fn find_biber_source<'a>(path: &'a str, sources: &[&'a str]) -> &'a str {
+    let file_name = |path: &'a str| Path::new(path).file_name().and_then(|name| name.to_str());
+    let name = file_name(path).unwrap_or(path);
+    let name = BIBER_TEMP_FILE_REGEX
+        .captures(name)
+        .and_then(|captures| Some(captures.name("name")?.as_str()))
+        .unwrap_or(name);
+
+    sources
+        .iter()
+        .copied()
+        .find(|&source| file_name(source) == Some(name))
+        .unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_blg;
+
+    #[test]
+    fn test_parse() {
+        insta::glob!("test_data/blg/*.txt", |path| {
+            let text = std::fs::read_to_string(path).unwrap().replace("\r\n", "\n");
+            insta::assert_debug_snapshot!(parse_blg(&text));
+        });
+    }
+}
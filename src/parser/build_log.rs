@@ -1,9 +1,9 @@
 use std::{cmp::Ordering, path::PathBuf};
 
 use once_cell::sync::Lazy;
-use regex::{Match, Regex};
+use regex::{Captures, Match, Regex};
 
-use crate::syntax::{BuildError, BuildErrorLevel, BuildLog};
+use crate::syntax::{BuildError, BuildErrorKind, BuildErrorLevel, BuildLog};
 
 const MAX_LINE_LENGTH: usize = 79;
 
@@ -17,17 +17,36 @@ static TEX_ERROR_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new("(?m)^! ((?P<msg1>(.|\r|\n)*?)\r?\nl\\.(?P<line>\\d+)( (\\.\\.\\.)?(?P<hint>[^\r\n]+))?|(?P<msg2>[^\r\n]*))").unwrap()
 });
 
-static WARNING_REGEX: Lazy<Regex> =
-//    Lazy::new(|| Regex::new("(LaTeX|Package [a-zA-Z_\\-]+) Warning: (?P<msg>[^\r\n]*on input line (?P<line>\\d+))").unwrap());
-    Lazy::new(|| Regex::new("(LaTeX|Package [a-zA-Z_\\-]+) Warning: (?P<msg>([^\r\n]*on input line (?P<line>\\d+)|[^\r\n]*))").unwrap());
+static LUA_ERROR_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new("(?m)^(?P<msg>(\\[\\\\directlua\\]|[^\\s()]+\\.lua):\\d+: [^\r\n]*)\r?\n(stack traceback:\r?\n(\t[^\r\n]*\r?\n)*)?l\\.(?P<line>\\d+)( (\\.\\.\\.)?(?P<hint>[^\r\n]+))?").unwrap()
+});
+
+static FILE_START_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new("^\\((?P<file>[^\r\n()]+\\.(tex|sty|cls))").unwrap());
+
+static LINE_NUMBER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new("^l\\.(?P<line>\\d+)( (\\.\\.\\.)?(?P<hint>[^\r\n]+))?").unwrap());
+
+static WARNING_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new("(?P<source>LaTeX|LaTeX Font|Package [a-zA-Z_\\-]+|Class [a-zA-Z_\\-]+) Warning: (?P<msg>([^\r\n]*on input line (?P<line>\\d+)|[^\r\n]*))").unwrap()
+});
+
+static UNDEFINED_REFERENCE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new("^Reference [`'](?P<key>[^`']+)' on page \\S+ undefined").unwrap());
+
+static UNDEFINED_CITATION_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new("^Citation [`'](?P<key>[^`']+)' on page \\S+ undefined").unwrap());
 
-// static UNDEF_REFERENCE_REGEX: Lazy<Regex> =
-//     Lazy::new(|| Regex::new("LaTeX Warning: (?P<msg>Reference `[^\r\n]*` on page \\d\\+ undefined on input line (?<line>\\d+)\\.[\r\n])").unwrap());
+static MULTIPLY_DEFINED_LABEL_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new("^Label [`'](?P<key>[^`']+)' multiply defined").unwrap());
 
 static BAD_BOX_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new("(?P<msg>(Ov|Und)erfull \\\\[hv]box[^\r\n]*lines? (?P<line>\\d+)[^\r\n]*)").unwrap()
 });
 
+static MISSING_CHARACTER_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new("(?P<msg>Missing character: [^\r\n]*)").unwrap());
+
 pub fn parse_build_log(log: &str) -> BuildLog {
     let log = prepare_log(log);
     let mut ranges: Vec<FileRange> = FILE_REGEX
@@ -36,21 +55,72 @@ pub fn parse_build_log(log: &str) -> BuildLog {
         .collect();
     ranges.sort();
 
-    let tex_errors = extract_matches(&log, &ranges, &TEX_ERROR_REGEX, BuildErrorLevel::Error);
-    let warnings = extract_matches(&log, &ranges, &WARNING_REGEX, BuildErrorLevel::Warning);
-//    let undef_references = extract_matches(&log, &ranges, &UNDEF_REFERENCE_REGEX, BuildErrorLevel::Warning);
-    let bad_boxes = extract_matches(&log, &ranges, &BAD_BOX_REGEX, BuildErrorLevel::Warning);
+    let extract = |regex: &Regex, level, classify: Classifier| {
+        extract_matches(&log, &ranges, regex, level, classify)
+    };
+
+    let errors = vec![
+        extract(&TEX_ERROR_REGEX, BuildErrorLevel::Error, classify_error),
+        extract(&LUA_ERROR_REGEX, BuildErrorLevel::Error, |_, _| {
+            BuildErrorKind::LuaError
+        }),
+        extract(&WARNING_REGEX, BuildErrorLevel::Warning, classify_warning),
+        extract(&BAD_BOX_REGEX, BuildErrorLevel::Warning, |_, _| {
+            BuildErrorKind::BadBox
+        }),
+        extract(
+            &MISSING_CHARACTER_REGEX,
+            BuildErrorLevel::Warning,
+            |_, _| BuildErrorKind::MissingCharacter,
+        ),
+    ]
+    .concat();
 
-//    let errors = vec![tex_errors, warnings, undef_references, bad_boxes].concat();
-    let errors = vec![tex_errors, warnings, bad_boxes].concat();
     BuildLog { errors }
 }
 
+/// Determines the kind of a message given the captures of the regex which found it.
+type Classifier = fn(&Captures, &str) -> BuildErrorKind;
+
+fn classify_error(_: &Captures, message: &str) -> BuildErrorKind {
+    if message.starts_with("LuaTeX error") {
+        BuildErrorKind::LuaError
+    } else {
+        BuildErrorKind::Error
+    }
+}
+
+fn classify_warning(captures: &Captures, message: &str) -> BuildErrorKind {
+    let source = &captures["source"];
+    let key = |regex: &Regex| {
+        regex
+            .captures(message)
+            .map(|captures| captures["key"].to_string())
+    };
+
+    if source == "LaTeX Font" {
+        BuildErrorKind::Font
+    } else if source.starts_with("Class ") {
+        BuildErrorKind::Class
+    } else if source == "Package hyperref" && message.starts_with("Token not allowed") {
+        BuildErrorKind::PdfStringToken
+    } else if let Some(key) = key(&UNDEFINED_REFERENCE_REGEX) {
+        BuildErrorKind::UndefinedReference(key)
+    } else if let Some(key) = key(&UNDEFINED_CITATION_REGEX) {
+        BuildErrorKind::UndefinedCitation(key)
+    } else if let Some(key) = key(&MULTIPLY_DEFINED_LABEL_REGEX) {
+        BuildErrorKind::MultiplyDefinedLabel(key)
+    } else {
+        BuildErrorKind::Warning
+    }
+}
+
 fn extract_matches(
     log: &str,
     ranges: &[FileRange],
     regex: &Regex,
     level: BuildErrorLevel,
+    classify: Classifier,
 ) -> Vec<BuildError> {
     let mut errors = Vec::new();
     for result in regex.find_iter(log) {
@@ -82,6 +152,7 @@ fn extract_matches(
             errors.push(BuildError {
                 relative_path: range.path.clone(),
                 level,
+                kind: classify(&captures, &message),
                 message,
                 line,
                 hint,
@@ -124,6 +195,76 @@ fn prepare_log(log: &str) -> String {
     new_lines.join("\n")
 }
 
+/// Extracts errors from the output of a running build one line at a time.
+/// Only errors with a line number (`! ...` followed by `l.<n>`) are reported.
+#[derive(Debug, Default)]
+pub struct BuildLogStream {
+    files: Vec<Option<PathBuf>>,
+    wrapped_line: String,
+    message: Option<String>,
+    in_context: bool,
+}
+
+impl BuildLogStream {
+    pub fn push_line(&mut self, line: &str) -> Option<BuildError> {
+        if line.chars().count() == MAX_LINE_LENGTH {
+            self.wrapped_line.push_str(line);
+            return None;
+        }
+
+        let line = std::mem::take(&mut self.wrapped_line) + line;
+        if let Some(message) = line.strip_prefix("! ") {
+            self.message = Some(message.to_string());
+            return None;
+        }
+
+        // The context of an error contains source code, which must not affect the file stack.
+        if self.in_context && line.starts_with(char::is_whitespace) {
+            self.in_context = false;
+            return None;
+        }
+
+        self.in_context = false;
+        if self.message.is_none() {
+            self.track_files(&line);
+            return None;
+        }
+
+        let captures = LINE_NUMBER_REGEX.captures(&line)?;
+        let message = self.message.take()?;
+        self.in_context = true;
+
+        let relative_path = self.files.iter().rev().flatten().next()?.clone();
+        let hint = captures
+            .name("hint")
+            .map(|hint| hint.as_str().trim().to_string())
+            .filter(|hint| !hint.is_empty());
+
+        Some(BuildError {
+            relative_path,
+            level: BuildErrorLevel::Error,
+            kind: classify_error(&captures, &message),
+            message,
+            hint,
+            line: captures["line"].parse::<u32>().ok()?.checked_sub(1),
+        })
+    }
+
+    fn track_files(&mut self, line: &str) {
+        for (index, c) in line.char_indices() {
+            if c == '(' {
+                let path = FILE_START_REGEX
+                    .captures(&line[index..])
+                    .map(|captures| PathBuf::from(&captures["file"]));
+
+                self.files.push(path);
+            } else if c == ')' {
+                self.files.pop();
+            }
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 struct FileRange {
     pub path: PathBuf,
@@ -181,7 +322,11 @@ impl PartialOrd for FileRange {
 
 #[cfg(test)]
 mod tests {
-    use super::parse_build_log;
+    use std::path::PathBuf;
+
+    use crate::syntax::{BuildError, BuildErrorKind, BuildErrorLevel};
+
+    use super::{parse_build_log, BuildLogStream};
 
     #[test]
     fn test_parse() {
@@ -190,4 +335,61 @@ mod tests {
             insta::assert_debug_snapshot!(parse_build_log(&text));
         });
     }
+
+    fn parse_stream(output: &str) -> Vec<BuildError> {
+        let mut stream = BuildLogStream::default();
+        output
+            .lines()
+            .filter_map(|line| stream.push_line(line))
+            .collect()
+    }
+
+    #[test]
+    fn test_stream_error() {
+        let output = r#"(./main.tex
+LaTeX2e <2022-11-01>
+(/usr/share/texmf/tex/latex/base/article.cls
+Document Class: article 2022/07/02 v1.4n Standard LaTeX document class
+) (./chapter.tex
+! Undefined control sequence.
+l.3 \foo
+         (bar)
+)
+! LaTeX Error: Environment foo undefined.
+
+See the LaTeX manual or LaTeX Companion for explanation.
+Type  H <return>  for immediate help.
+ ...
+
+l.7 \begin{foo}
+"#;
+
+        assert_eq!(
+            parse_stream(output),
+            vec![
+                BuildError {
+                    relative_path: PathBuf::from("./chapter.tex"),
+                    level: BuildErrorLevel::Error,
+                    kind: BuildErrorKind::Error,
+                    message: "Undefined control sequence.".to_string(),
+                    hint: Some("\\foo".to_string()),
+                    line: Some(2),
+                },
+                BuildError {
+                    relative_path: PathBuf::from("./main.tex"),
+                    level: BuildErrorLevel::Error,
+                    kind: BuildErrorKind::Error,
+                    message: "LaTeX Error: Environment foo undefined.".to_string(),
+                    hint: Some("\\begin{foo}".to_string()),
+                    line: Some(6),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stream_without_line() {
+        let output = "(./main.tex\n! Emergency stop.\n<*> main.tex\n";
+        assert_eq!(parse_stream(output), Vec::new());
+    }
 }
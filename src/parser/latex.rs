@@ -132,6 +132,7 @@ impl<'a> Parser<'a> {
             LABEL_REFERENCE_RANGE_NAME => self.label_reference_range(),
             LABEL_NUMBER_NAME => self.label_number(),
             COMMAND_DEFINITION_NAME => self.command_definition(),
+            DOCUMENT_COMMAND_DEFINITION_NAME => self.document_command_definition(),
             MATH_OPERATOR_NAME => self.math_operator(),
             GLOSSARY_ENTRY_DEFINITION_NAME => self.glossary_entry_definition(),
             GLOSSARY_ENTRY_REFERENCE_NAME => self.glossary_entry_reference(),
@@ -142,6 +143,7 @@ impl<'a> Parser<'a> {
             COLOR_REFERENCE_NAME => self.color_reference(),
             COLOR_DEFINITION_NAME => self.color_definition(),
             COLOR_SET_DEFINITION_NAME => self.color_set_definition(),
+            COLOR_LET_DEFINITION_NAME => self.color_let_definition(),
             TIKZ_LIBRARY_IMPORT_NAME => self.tikz_library_import(),
             ENVIRONMENT_DEFINITION_NAME => self.environment_definition(),
             BEGIN_BLOCK_COMMENT_NAME => self.block_comment(),
@@ -1039,6 +1041,32 @@ impl<'a> Parser<'a> {
         self.builder.finish_node();
     }
 
+    fn document_command_definition(&mut self) {
+        self.builder.start_node(DOCUMENT_COMMAND_DEFINITION.into());
+        self.eat();
+        self.trivia();
+
+        if self.lexer.peek() == Some(L_CURLY) {
+            self.curly_group_command();
+        } else {
+            self.builder.token(MISSING.into(), "");
+        }
+
+        if self.lexer.peek() == Some(L_CURLY) {
+            self.curly_group_without_environments();
+        } else {
+            self.builder.token(MISSING.into(), "");
+        }
+
+        if self.lexer.peek() == Some(L_CURLY) {
+            self.curly_group_impl();
+        } else {
+            self.builder.token(MISSING.into(), "");
+        }
+
+        self.builder.finish_node();
+    }
+
     fn math_operator(&mut self) {
         self.builder.start_node(MATH_OPERATOR.into());
         self.eat();
@@ -1255,6 +1283,22 @@ impl<'a> Parser<'a> {
         self.builder.finish_node();
     }
 
+    fn color_let_definition(&mut self) {
+        self.builder.start_node(COLOR_LET_DEFINITION.into());
+        self.eat();
+        self.trivia();
+
+        for _ in 0..2 {
+            if self.lexer.peek() == Some(L_CURLY) {
+                self.curly_group_word();
+            } else {
+                self.builder.token(MISSING.into(), "");
+            }
+        }
+
+        self.builder.finish_node();
+    }
+
     fn tikz_library_import(&mut self) {
         self.builder.start_node(TIKZ_LIBRARY_IMPORT.into());
         self.eat();
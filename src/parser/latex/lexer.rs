@@ -245,6 +245,12 @@ enum CommandNameToken {
     #[token("\\DeclareRobustCommand*")]
     CommandDefinition,
 
+    #[token("\\NewDocumentCommand")]
+    #[token("\\RenewDocumentCommand")]
+    #[token("\\ProvideDocumentCommand")]
+    #[token("\\DeclareDocumentCommand")]
+    DocumentCommandDefinition,
+
     #[token("\\DeclareMathOperator")]
     #[token("\\DeclareMathOperator*")]
     MathOperator,
@@ -406,6 +412,9 @@ enum CommandNameToken {
     #[token("\\definecolorset")]
     ColorSetDefinition,
 
+    #[token("\\colorlet")]
+    ColorLetDefinition,
+
     #[token("\\usepgflibrary")]
     #[token("\\usetikzlibrary")]
     TikzLibraryImport,
@@ -522,6 +531,14 @@ fn tokenize<'a>(input: &'a str, tokens: &mut Vec<(SyntaxKind, &'a str)>) {
             RootToken::CommandName => {
                 let kind = tokenize_command_name(text);
                 tokens.push((kind, text));
+                if matches!(text, "\\verb" | "\\verb*" | "\\lstinline" | "\\mintinline") {
+                    let end = lexer.span().end;
+                    lexer = RootToken::lexer(tokenize_inline_verbatim(
+                        text,
+                        &lexer.source()[end..],
+                        tokens,
+                    ));
+                }
             }
             RootToken::BeginBlockComment => {
                 tokens.push((SyntaxKind::BEGIN_BLOCK_COMMENT_NAME, text));
@@ -575,6 +592,7 @@ fn tokenize_command_name(text: &str) -> SyntaxKind {
         CommandNameToken::LabelReferenceRange => SyntaxKind::LABEL_REFERENCE_RANGE_NAME,
         CommandNameToken::LabelNumber => SyntaxKind::LABEL_NUMBER_NAME,
         CommandNameToken::CommandDefinition => SyntaxKind::COMMAND_DEFINITION_NAME,
+        CommandNameToken::DocumentCommandDefinition => SyntaxKind::DOCUMENT_COMMAND_DEFINITION_NAME,
         CommandNameToken::MathOperator => SyntaxKind::MATH_OPERATOR_NAME,
         CommandNameToken::GlossaryEntryDefinition => SyntaxKind::GLOSSARY_ENTRY_DEFINITION_NAME,
         CommandNameToken::GlossaryEntryReference => SyntaxKind::GLOSSARY_ENTRY_REFERENCE_NAME,
@@ -585,6 +603,7 @@ fn tokenize_command_name(text: &str) -> SyntaxKind {
         CommandNameToken::ColorReference => SyntaxKind::COLOR_REFERENCE_NAME,
         CommandNameToken::ColorDefinition => SyntaxKind::COLOR_DEFINITION_NAME,
         CommandNameToken::ColorSetDefinition => SyntaxKind::COLOR_SET_DEFINITION_NAME,
+        CommandNameToken::ColorLetDefinition => SyntaxKind::COLOR_LET_DEFINITION_NAME,
         CommandNameToken::TikzLibraryImport => SyntaxKind::TIKZ_LIBRARY_IMPORT_NAME,
         CommandNameToken::EnvironmentDefinition => SyntaxKind::ENVIRONMENT_DEFINITION_NAME,
         CommandNameToken::EndBlockComment => SyntaxKind::END_BLOCK_COMMENT_NAME,
@@ -639,3 +658,57 @@ fn tokenize_verbatim_environment<'a>(
 
     &input[end..]
 }
+
+fn tokenize_inline_verbatim<'a>(
+    command: &str,
+    input: &'a str,
+    tokens: &mut Vec<(SyntaxKind, &'a str)>,
+) -> &'a str {
+    match inline_verbatim_length(command, input) {
+        Some(end) => {
+            tokens.push((SyntaxKind::VERBATIM, &input[..end]));
+            &input[end..]
+        }
+        None => input,
+    }
+}
+
+/// Finds the end of the argument of `\verb|...|` and similar commands.
+/// The options of `\lstinline` and the language of `\mintinline` are part of the verbatim text.
+fn inline_verbatim_length(command: &str, input: &str) -> Option<usize> {
+    let mut start = 0;
+    if matches!(command, "\\lstinline" | "\\mintinline") {
+        if input.starts_with('[') {
+            start = input.find(']')? + 1;
+        }
+
+        if command == "\\mintinline" {
+            let language = input[start..].strip_prefix('{')?;
+            start += language.find('}')? + 2;
+        }
+    }
+
+    let delimiter = input[start..]
+        .chars()
+        .next()
+        .filter(|c| !c.is_whitespace())?;
+    let closing = if delimiter == '{' && !command.starts_with("\\verb") {
+        '}'
+    } else {
+        delimiter
+    };
+
+    let body = start + delimiter.len_utf8();
+    let mut depth = 0;
+    for (index, c) in input[body..].char_indices() {
+        match c {
+            '\r' | '\n' => return None,
+            _ if c == closing && depth == 0 => return Some(body + index + c.len_utf8()),
+            '{' if closing == '}' => depth += 1,
+            '}' if closing == '}' => depth -= 1,
+            _ => {}
+        };
+    }
+
+    None
+}
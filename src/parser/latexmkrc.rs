@@ -0,0 +1,57 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::syntax::Latexmkrc;
+
+static ASSIGNMENT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new("(?m)^\\s*\\$(?P<name>out_dir|aux_dir|pdf_mode)\\s*=\\s*(?P<value>'[^'\r\n]*'|\"[^\"\r\n]*\"|[^\\s;#]+)").unwrap()
+});
+
+/// Extracts the simple assignments of a `latexmkrc` file, which is a Perl script.
+/// Later assignments override earlier ones like they would when running the script.
+pub fn parse_latexmkrc(text: &str) -> Latexmkrc {
+    let mut config = Latexmkrc::default();
+    for captures in ASSIGNMENT_REGEX.captures_iter(text) {
+        let value = captures["value"].trim_matches(|c| c == '\'' || c == '"');
+        match &captures["name"] {
+            "out_dir" => config.out_dir = Some(value.to_string()),
+            "aux_dir" => config.aux_dir = Some(value.to_string()),
+            _ => config.pdf_mode = value.parse().ok(),
+        };
+    }
+
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::syntax::Latexmkrc;
+
+    use super::parse_latexmkrc;
+
+    #[test]
+    fn test_directories() {
+        let text =
+            "# Build into a separate directory\n$out_dir = 'build';\n$aux_dir = \"build/aux\";\n";
+        assert_eq!(
+            parse_latexmkrc(text),
+            Latexmkrc {
+                out_dir: Some("build".to_string()),
+                aux_dir: Some("build/aux".to_string()),
+                pdf_mode: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_pdf_mode() {
+        let text = "$pdf_mode = 1;\n$pdf_mode = 4; # lualatex\n";
+        assert_eq!(parse_latexmkrc(text).pdf_mode, Some(4));
+    }
+
+    #[test]
+    fn test_comment() {
+        let text = "# $out_dir = 'build';\n@default_files = ('main.tex');\n";
+        assert_eq!(parse_latexmkrc(text), Latexmkrc::default());
+    }
+}
@@ -0,0 +1,225 @@
+use std::path::PathBuf;
+
+use crate::syntax::synctex::{Node, NodeKind, Page, SyncTex};
+
+/// The number of scaled points per PDF point.
+const SP_PER_BP: f64 = 65781.76;
+
+pub fn parse_synctex(text: &str) -> SyncTex {
+    let mut synctex = SyncTex::default();
+    let mut unit = 1.0;
+    let mut magnification = 1000.0;
+    let mut x_offset = 0.0;
+    let mut y_offset = 0.0;
+    let mut page: Option<Page> = None;
+
+    for line in text.lines() {
+        if let Some(input) = line.strip_prefix("Input:") {
+            if let Some((tag, path)) = input.split_once(':') {
+                if let Ok(tag) = tag.parse() {
+                    synctex.inputs.insert(tag, PathBuf::from(path));
+                }
+            }
+        } else if let Some(value) = line.strip_prefix("Unit:") {
+            unit = value.trim().parse().unwrap_or(unit);
+        } else if let Some(value) = line.strip_prefix("Magnification:") {
+            magnification = value.trim().parse().unwrap_or(magnification);
+        } else if let Some(value) = line.strip_prefix("X Offset:") {
+            x_offset = value.trim().parse().unwrap_or(x_offset);
+        } else if let Some(value) = line.strip_prefix("Y Offset:") {
+            y_offset = value.trim().parse().unwrap_or(y_offset);
+        } else if let Some(number) = line.strip_prefix('{') {
+            page = number.parse().ok().map(|number| Page {
+                number,
+                nodes: Vec::new(),
+            });
+        } else if line.starts_with('}') {
+            synctex.pages.extend(page.take());
+        } else if let Some(page) = &mut page {
+            page.nodes.extend(parse_node(line));
+        }
+    }
+
+    // The post scriptum may change the magnification and the offsets, so the nodes are converted last.
+    // The offsets are given in the same unit as the records and are magnified along with them.
+    let scale = unit * magnification / 1000.0 / SP_PER_BP;
+    for node in synctex.pages.iter_mut().flat_map(|page| &mut page.nodes) {
+        node.x = (node.x + x_offset) * scale;
+        node.y = (node.y + y_offset) * scale;
+        node.width *= scale;
+        node.height *= scale;
+        node.depth *= scale;
+    }
+
+    synctex
+}
+
+fn parse_node(line: &str) -> Option<Node> {
+    let mut chars = line.chars();
+    let kind = match chars.next()? {
+        '[' => NodeKind::VBox,
+        '(' => NodeKind::HBox,
+        'v' => NodeKind::VoidVBox,
+        'h' => NodeKind::VoidHBox,
+        'x' => NodeKind::Current,
+        'k' => NodeKind::Kern,
+        'g' => NodeKind::Glue,
+        '$' => NodeKind::Math,
+        _ => return None,
+    };
+
+    let mut parts = chars.as_str().split(':');
+    let mut link = parts.next()?.split(',');
+    let tag = link.next()?.parse().ok()?;
+    let line = link.next()?.parse().ok()?;
+
+    let point = parse_numbers(parts.next()?)?;
+    let size = match parts.next() {
+        Some(text) => parse_numbers(text)?,
+        None => Vec::new(),
+    };
+
+    let dimension = |index: usize| size.get(index).copied().unwrap_or(0.0);
+    Some(Node {
+        kind,
+        tag,
+        line,
+        x: *point.first()?,
+        y: *point.get(1)?,
+        width: dimension(0),
+        height: dimension(1),
+        depth: dimension(2),
+    })
+}
+
+fn parse_numbers(text: &str) -> Option<Vec<f64>> {
+    text.split(',').map(|number| number.parse().ok()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use crate::syntax::synctex::Area;
+
+    use super::parse_synctex;
+
+    const SYNCTEX: &str = r#"SyncTeX Version:1
+Input:1:/home/user/main.tex
+Input:2:./chapter.tex
+Output:pdf
+Magnification:1000
+Unit:1
+X Offset:0
+Y Offset:0
+Content:
+!162
+{1
+[1,3:6578176,13156352:26312704,39468672,0
+(1,3:6578176,13156352:26312704,1315635,328909
+x1,3:6578176,13156352
+)
+(2,5:6578176,19734528:13156352,1315635,328909
+)
+]
+!42
+}1
+{2
+[1,7:6578176,13156352:26312704,39468672,0
+(1,7:6578176,13156352:6578176,1315635,0
+)
+]
+}2
+Input:3:/home/user/other.tex
+Postamble:
+Count:9
+!25
+Post scriptum:
+"#;
+
+    fn assert_area(actual: Option<Area>, expected: Area) {
+        let actual = actual.unwrap();
+        assert_eq!(actual.page, expected.page);
+        for (a, b) in [
+            (actual.x, expected.x),
+            (actual.y, expected.y),
+            (actual.width, expected.width),
+            (actual.height, expected.height),
+        ] {
+            assert!((a - b).abs() < 0.01, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn test_inputs() {
+        let synctex = parse_synctex(SYNCTEX);
+        assert_eq!(synctex.inputs.len(), 3);
+        assert_eq!(synctex.inputs[&2], Path::new("./chapter.tex"));
+        assert_eq!(synctex.pages.len(), 2);
+    }
+
+    #[test]
+    fn test_forward_exact() {
+        assert_area(
+            parse_synctex(SYNCTEX).forward(1, 3),
+            Area {
+                page: 1,
+                x: 100.0,
+                y: 180.0,
+                width: 400.0,
+                height: 25.0,
+            },
+        );
+    }
+
+    #[test]
+    fn test_forward_closest_line() {
+        assert_area(
+            parse_synctex(SYNCTEX).forward(1, 6),
+            Area {
+                page: 2,
+                x: 100.0,
+                y: 180.0,
+                width: 100.0,
+                height: 20.0,
+            },
+        );
+    }
+
+    #[test]
+    fn test_inverse_smallest_box() {
+        let synctex = parse_synctex(SYNCTEX);
+        let node = synctex.inverse(1, 150.0, 190.0).unwrap();
+        assert_eq!((node.tag, node.line), (1, 3));
+
+        let node = synctex.inverse(1, 150.0, 290.0).unwrap();
+        assert_eq!((node.tag, node.line), (2, 5));
+    }
+
+    #[test]
+    fn test_unit_and_magnification() {
+        let text = SYNCTEX
+            .replace("Magnification:1000", "Magnification:2000")
+            .replace("Unit:1", "Unit:4")
+            .replace(
+                "Post scriptum:",
+                "Post scriptum:\nX Offset:822272\nY Offset:-822272",
+            );
+
+        assert_area(
+            parse_synctex(&text).forward(1, 3),
+            Area {
+                page: 1,
+                x: 900.0,
+                y: 1340.0,
+                width: 3200.0,
+                height: 200.0,
+            },
+        );
+    }
+
+    #[test]
+    fn test_inverse_unknown_page() {
+        assert_eq!(parse_synctex(SYNCTEX).inverse(3, 0.0, 0.0), None);
+    }
+}
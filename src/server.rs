@@ -1,17 +1,13 @@
 mod dispatch;
 mod query;
 
-use std::{
-    path::PathBuf,
-    sync::{Arc, Mutex},
-};
+use std::{path::PathBuf, sync::Arc};
 
 use anyhow::Result;
 use crossbeam_channel::{Receiver, Sender};
 use log::{error, info};
 use lsp_server::{Connection, ErrorCode, Message, RequestId};
 use lsp_types::{notification::*, request::*, *};
-use once_cell::sync::Lazy;
 use rowan::{ast::AstNode, TextSize};
 use rustc_hash::FxHashSet;
 use serde::{Deserialize, Serialize};
@@ -24,13 +20,17 @@ use crate::{
     db::{self, discover_dependencies, Document, Language, Owner, Workspace},
     distro::Distro,
     features::{
-        build::{self, BuildParams, BuildResult, BuildStatus},
+        build::{self, BuildManager, BuildParams, BuildResult, BuildStatus},
+        code_action, color,
         completion::{self, builder::CompletionItemData},
         definition, folding, formatting, forward_search, highlight, hover, inlay_hint, link,
-        reference, rename, symbol, workspace_command,
+        linked_editing, reference, rename, selection_range, semantic_tokens, signature_help,
+        symbol,
+        synctex::{self, InverseSearchParams},
+        workspace_command,
     },
     normalize_uri,
-    syntax::bibtex,
+    syntax::{self, bibtex},
     util::{
         self, capabilities::ClientCapabilitiesExt, components::COMPONENT_DATABASE,
         line_index_ext::LineIndexExt,
@@ -46,6 +46,8 @@ enum InternalMessage {
     ForwardSearch(Url),
     Diagnostics,
     ChktexResult(Url, Vec<db::diagnostics::Diagnostic>),
+    BuildError(Url, syntax::BuildError),
+    BuildFinished(Url),
 }
 
 pub struct Server {
@@ -56,6 +58,7 @@ pub struct Server {
     engine: query::Engine,
     watcher: FileWatcher,
     pool: ThreadPool,
+    builds: BuildManager,
 }
 
 impl Server {
@@ -71,6 +74,7 @@ impl Server {
             engine: query::Engine::default(),
             watcher,
             pool: threadpool::Builder::new().build(),
+            builds: BuildManager::default(),
         }
     }
 
@@ -127,14 +131,32 @@ impl Server {
             })),
             document_highlight_provider: Some(OneOf::Left(true)),
             document_formatting_provider: Some(OneOf::Left(true)),
+            code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
             execute_command_provider: Some(ExecuteCommandOptions {
                 commands: vec![
                     "texlab.cleanAuxiliary".into(),
                     "texlab.cleanArtifacts".into(),
+                    "texlab.cancelBuild".into(),
                 ],
                 ..Default::default()
             }),
             inlay_hint_provider: Some(OneOf::Left(true)),
+            selection_range_provider: Some(SelectionRangeProviderCapability::Simple(true)),
+            linked_editing_range_provider: Some(LinkedEditingRangeServerCapabilities::Simple(true)),
+            signature_help_provider: Some(SignatureHelpOptions {
+                trigger_characters: Some(vec!["{".into(), "[".into()]),
+                retrigger_characters: Some(vec!["}".into(), "]".into()]),
+                work_done_progress_options: WorkDoneProgressOptions::default(),
+            }),
+            color_provider: Some(ColorProviderCapability::Simple(true)),
+            semantic_tokens_provider: Some(
+                SemanticTokensServerCapabilities::SemanticTokensOptions(SemanticTokensOptions {
+                    legend: semantic_tokens::legend(),
+                    range: Some(true),
+                    full: Some(SemanticTokensFullOptions::Bool(true)),
+                    work_done_progress_options: WorkDoneProgressOptions::default(),
+                }),
+            ),
             ..ServerCapabilities::default()
         }
     }
@@ -314,7 +336,13 @@ impl Server {
         self.watcher.watch(db);
     }
 
-    fn cancel(&self, _params: CancelParams) -> Result<()> {
+    fn cancel(&self, params: CancelParams) -> Result<()> {
+        let id = match params.id {
+            NumberOrString::Number(id) => RequestId::from(id),
+            NumberOrString::String(id) => RequestId::from(id),
+        };
+
+        self.builds.cancel_request(&id);
         Ok(())
     }
 
@@ -414,7 +442,7 @@ impl Server {
         let db = self.engine.read();
         let workspace = Workspace::get(db);
         if workspace.options(db).build.on_save {
-            self.build_internal(uri.clone(), |_| ())?;
+            self.build_internal(uri.clone(), None, None, |_| ())?;
         }
 
         self.publish_diagnostics_with_delay();
@@ -459,6 +487,36 @@ impl Server {
         }
     }
 
+    fn add_build_diagnostic(&mut self, root: &Url, error: &syntax::BuildError) {
+        let db = self.engine.write();
+        let workspace = Workspace::get(db);
+        let code = db::diagnostics::DiagnosticCode::Build;
+        if let Some((document, diagnostic)) = workspace
+            .lookup_uri(db, root)
+            .and_then(|root| db::diagnostics::log::resolve(db, workspace, root, error, code))
+        {
+            let mut diagnostics = document.linter(db).build(db).clone();
+            diagnostics.push(diagnostic);
+            document.linter(db).set_build(db).to(diagnostics);
+        }
+    }
+
+    /// Removes the diagnostics of a finished build, which are superseded by the log file.
+    fn clear_build_diagnostics(&mut self, root: &Url) {
+        let db = self.engine.write();
+        let workspace = Workspace::get(db);
+        let documents: Vec<_> = match workspace.lookup_uri(db, root) {
+            Some(root) => workspace.related(db, root).iter().copied().collect(),
+            None => return,
+        };
+
+        for document in documents {
+            if !document.linter(db).build(db).is_empty() {
+                document.linter(db).set_build(db).to(Vec::new());
+            }
+        }
+    }
+
     fn document_link(&self, id: RequestId, params: DocumentLinkParams) -> Result<()> {
         let mut uri = params.text_document.uri;
         normalize_uri(&mut uri);
@@ -618,7 +676,22 @@ impl Server {
         Ok(())
     }
 
+    fn code_actions(&self, id: RequestId, params: CodeActionParams) -> Result<()> {
+        let mut uri = params.text_document.uri;
+        normalize_uri(&mut uri);
+        self.run_with_db(id, move |db| {
+            code_action::find_all(db, &uri, params.range, &params.context.diagnostics)
+                .unwrap_or_default()
+        });
+
+        Ok(())
+    }
+
     fn execute_command(&mut self, id: RequestId, params: ExecuteCommandParams) -> Result<()> {
+        if params.command == "texlab.cancelBuild" {
+            return self.cancel_build(id, params.arguments);
+        }
+
         let db = self.engine.read();
         match workspace_command::select(db, &params.command, params.arguments) {
             Ok(command) => {
@@ -648,6 +721,35 @@ impl Server {
         Ok(())
     }
 
+    fn cancel_build(&self, id: RequestId, arguments: Vec<serde_json::Value>) -> Result<()> {
+        let db = self.engine.read();
+        match arguments.into_iter().next() {
+            Some(argument) => match serde_json::from_value::<TextDocumentIdentifier>(argument) {
+                Ok(mut params) => {
+                    normalize_uri(&mut params.uri);
+                    let root = build::find_root(db, &params.uri)
+                        .map_or(params.uri, |root| root.location(db).uri(db).clone());
+
+                    self.builds.cancel(Some(&root));
+                }
+                Err(why) => {
+                    self.client
+                        .send_error(id, ErrorCode::InvalidParams, why.to_string())
+                        .unwrap();
+
+                    return Ok(());
+                }
+            },
+            None => self.builds.cancel(None),
+        };
+
+        self.client
+            .send_response(lsp_server::Response::new_ok(id, ()))
+            .unwrap();
+
+        Ok(())
+    }
+
     fn inlay_hints(&self, id: RequestId, params: InlayHintParams) -> Result<()> {
         let mut uri = params.text_document.uri;
         normalize_uri(&mut uri);
@@ -663,11 +765,64 @@ impl Server {
         Ok(())
     }
 
+    fn semantic_tokens_full(&self, id: RequestId, params: SemanticTokensParams) -> Result<()> {
+        let mut uri = params.text_document.uri;
+        normalize_uri(&mut uri);
+        self.run_with_db(id, move |db| semantic_tokens::find_all(db, &uri, None));
+        Ok(())
+    }
+
     fn semantic_tokens_range(
         &self,
-        _id: RequestId,
-        _params: SemanticTokensRangeParams,
+        id: RequestId,
+        params: SemanticTokensRangeParams,
     ) -> Result<()> {
+        let mut uri = params.text_document.uri;
+        normalize_uri(&mut uri);
+        self.run_with_db(id, move |db| {
+            semantic_tokens::find_all(db, &uri, Some(params.range))
+        });
+        Ok(())
+    }
+
+    fn selection_range(&self, id: RequestId, params: SelectionRangeParams) -> Result<()> {
+        let mut uri = params.text_document.uri;
+        normalize_uri(&mut uri);
+        self.run_with_db(id, move |db| {
+            selection_range::find_all(db, &uri, params.positions).unwrap_or_default()
+        });
+        Ok(())
+    }
+
+    fn linked_editing_range(&self, id: RequestId, params: LinkedEditingRangeParams) -> Result<()> {
+        let mut uri = params.text_document_position_params.text_document.uri;
+        normalize_uri(&mut uri);
+        let position = params.text_document_position_params.position;
+        self.run_with_db(id, move |db| linked_editing::find_all(db, &uri, position));
+        Ok(())
+    }
+
+    fn signature_help(&self, id: RequestId, params: SignatureHelpParams) -> Result<()> {
+        let mut uri = params.text_document_position_params.text_document.uri;
+        normalize_uri(&mut uri);
+        let position = params.text_document_position_params.position;
+        self.run_with_db(id, move |db| signature_help::find_all(db, &uri, position));
+        Ok(())
+    }
+
+    fn document_color(&self, id: RequestId, params: DocumentColorParams) -> Result<()> {
+        let mut uri = params.text_document.uri;
+        normalize_uri(&mut uri);
+        self.run_with_db(id, move |db| color::find_all(db, &uri).unwrap_or_default());
+        Ok(())
+    }
+
+    fn color_presentation(&self, id: RequestId, params: ColorPresentationParams) -> Result<()> {
+        let mut uri = params.text_document.uri;
+        normalize_uri(&mut uri);
+        self.run_with_db(id, move |db| {
+            color::find_presentations(db, &uri, params.range, params.color).unwrap_or_default()
+        });
         Ok(())
     }
 
@@ -676,8 +831,7 @@ impl Server {
         normalize_uri(&mut uri);
 
         let client = self.client.clone();
-        self.build_internal(uri, move |status| {
-            let result = BuildResult { status };
+        self.build_internal(uri, params.recipe, Some(id.clone()), move |result| {
             let _ = client.send_response(lsp_server::Response::new_ok(id, result));
         })?;
 
@@ -687,32 +841,45 @@ impl Server {
     fn build_internal(
         &mut self,
         uri: Url,
-        callback: impl FnOnce(BuildStatus) + Send + 'static,
+        recipe: Option<String>,
+        request_id: Option<RequestId>,
+        callback: impl FnOnce(BuildResult) + Send + 'static,
     ) -> Result<()> {
-        static LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
-
         let db = self.engine.read();
-        let compiler = match build::Command::new(db, uri.clone(), self.client.clone()) {
-            Some(compiler) => compiler,
-            None => {
-                callback(BuildStatus::FAILURE);
-                return Ok(());
-            }
-        };
+        let compiler =
+            match build::Command::new(db, uri.clone(), recipe.as_deref(), self.client.clone()) {
+                Some(compiler) => compiler,
+                None => {
+                    callback(BuildStatus::FAILURE.into());
+                    return Ok(());
+                }
+            };
 
         let forward_search_after = Workspace::get(db).options(db).build.forward_search_after;
 
+        // A new build of the same project cancels the running one and waits until it has stopped.
+        let builds = self.builds.clone();
+        let root = compiler.uri().clone();
+        let (handle, lock) = builds.start(&root, request_id);
+
         let sender = self.internal_tx.clone();
         self.pool.execute(move || {
-            let guard = LOCK.lock().unwrap();
+            let guard = lock.lock().unwrap();
 
-            let status = compiler.run();
-            if forward_search_after {
+            let error_sender = sender.clone();
+            let error_root = root.clone();
+            let result = compiler.run(&handle, move |error| {
+                let _ = error_sender.send(InternalMessage::BuildError(error_root.clone(), error));
+            });
+
+            drop(guard);
+            builds.finish(&root, &handle, lock);
+            let _ = sender.send(InternalMessage::BuildFinished(root));
+            if forward_search_after && result.status != BuildStatus::CANCELLED {
                 let _ = sender.send(InternalMessage::ForwardSearch(uri));
             }
 
-            drop(guard);
-            callback(status);
+            callback(result);
         });
 
         Ok(())
@@ -758,6 +925,25 @@ impl Server {
         Ok(())
     }
 
+    fn forward_search_position(
+        &self,
+        id: RequestId,
+        params: TextDocumentPositionParams,
+    ) -> Result<()> {
+        let mut uri = params.text_document.uri;
+        normalize_uri(&mut uri);
+        self.run_with_db(id, move |db| {
+            synctex::find_position(db, &uri, params.position)
+        });
+        Ok(())
+    }
+
+    fn inverse_search(&self, id: RequestId, mut params: InverseSearchParams) -> Result<()> {
+        normalize_uri(&mut params.text_document.uri);
+        self.run_with_db(id, move |db| synctex::find_source(db, &params));
+        Ok(())
+    }
+
     fn handle_file_event(&mut self, event: notify::Event) {
         let mut changed = false;
 
@@ -841,11 +1027,21 @@ impl Server {
                                     self.document_highlight(id, params)
                                 })?
                                 .on::<Formatting, _>(|id, params| self.formatting(id, params))?
+                                .on::<CodeActionRequest, _>(|id, params| self.code_actions(id, params))?
                                 .on::<BuildRequest, _>(|id, params| self.build(id, params))?
                                 .on::<ForwardSearchRequest, _>(|id, params| {
                                     self.forward_search(id, params)
                                 })?
+                                .on::<ForwardSearchPositionRequest, _>(|id, params| {
+                                    self.forward_search_position(id, params)
+                                })?
+                                .on::<InverseSearchRequest, _>(|id, params| {
+                                    self.inverse_search(id, params)
+                                })?
                                 .on::<ExecuteCommand,_>(|id, params| self.execute_command(id, params))?
+                                .on::<SemanticTokensFullRequest, _>(|id, params| {
+                                    self.semantic_tokens_full(id, params)
+                                })?
                                 .on::<SemanticTokensRangeRequest, _>(|id, params| {
                                     self.semantic_tokens_range(id, params)
                                 })?
@@ -855,6 +1051,19 @@ impl Server {
                                 .on::<InlayHintResolveRequest,_>(|id, params| {
                                     self.inlay_hint_resolve(id, params)
                                 })?
+                                .on::<SelectionRangeRequest, _>(|id, params| {
+                                    self.selection_range(id, params)
+                                })?
+                                .on::<LinkedEditingRange, _>(|id, params| {
+                                    self.linked_editing_range(id, params)
+                                })?
+                                .on::<SignatureHelpRequest, _>(|id, params| {
+                                    self.signature_help(id, params)
+                                })?
+                                .on::<DocumentColor, _>(|id, params| self.document_color(id, params))?
+                                .on::<ColorPresentationRequest, _>(|id, params| {
+                                    self.color_presentation(id, params)
+                                })?
                                 .default()
                             {
                                 self.connection.sender.send(response.into())?;
@@ -910,6 +1119,14 @@ impl Server {
 
                             self.publish_diagnostics()?;
                         }
+                        InternalMessage::BuildError(uri, error) => {
+                            self.add_build_diagnostic(&uri, &error);
+                            self.publish_diagnostics()?;
+                        }
+                        InternalMessage::BuildFinished(uri) => {
+                            self.clear_build_diagnostics(&uri);
+                            self.publish_diagnostics()?;
+                        }
                     };
                 }
             };
@@ -970,6 +1187,26 @@ impl lsp_types::request::Request for ForwardSearchRequest {
     const METHOD: &'static str = "textDocument/forwardSearch";
 }
 
+struct ForwardSearchPositionRequest;
+
+impl lsp_types::request::Request for ForwardSearchPositionRequest {
+    type Params = TextDocumentPositionParams;
+
+    type Result = Option<synctex::ForwardSearchPosition>;
+
+    const METHOD: &'static str = "texlab/forwardSearchPosition";
+}
+
+struct InverseSearchRequest;
+
+impl lsp_types::request::Request for InverseSearchRequest {
+    type Params = InverseSearchParams;
+
+    type Result = Option<Location>;
+
+    const METHOD: &'static str = "texlab/inverseSearch";
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize_repr, Deserialize_repr)]
 #[repr(i32)]
 pub enum ForwardSearchStatus {
@@ -1,5 +1,6 @@
 pub mod bibtex;
 pub mod latex;
+pub mod synctex;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
 pub enum BuildErrorLevel {
@@ -7,10 +8,38 @@ pub enum BuildErrorLevel {
     Warning,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, PartialOrd, Ord, Hash)]
+pub enum BuildErrorKind {
+    Error,
+    LuaError,
+    Warning,
+    BadBox,
+    UndefinedReference(String),
+    UndefinedCitation(String),
+    MultiplyDefinedLabel(String),
+    Font,
+    Class,
+    PdfStringToken,
+    MissingCharacter,
+}
+
+impl BuildErrorKind {
+    /// The label or citation key which is named by the message.
+    pub fn key(&self) -> Option<&str> {
+        match self {
+            Self::UndefinedReference(key)
+            | Self::UndefinedCitation(key)
+            | Self::MultiplyDefinedLabel(key) => Some(key),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub struct BuildError {
     pub relative_path: std::path::PathBuf,
     pub level: BuildErrorLevel,
+    pub kind: BuildErrorKind,
     pub message: String,
     pub hint: Option<String>,
     pub line: Option<u32>,
@@ -20,3 +49,11 @@ pub struct BuildError {
 pub struct BuildLog {
     pub errors: Vec<BuildError>,
 }
+
+/// The settings of a `latexmkrc` file which are relevant for finding the build artifacts.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Hash)]
+pub struct Latexmkrc {
+    pub out_dir: Option<String>,
+    pub aux_dir: Option<String>,
+    pub pdf_mode: Option<u8>,
+}
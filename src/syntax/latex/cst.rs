@@ -544,7 +544,24 @@ impl TheoremDefinition {
     }
 }
 
-cst_node!(CommandDefinition, COMMAND_DEFINITION, MATH_OPERATOR);
+cst_node!(EnvironmentDefinition, ENVIRONMENT_DEFINITION);
+
+impl EnvironmentDefinition {
+    pub fn command(&self) -> Option<SyntaxToken> {
+        self.syntax().first_token()
+    }
+
+    pub fn name(&self) -> Option<CurlyGroupWord> {
+        self.syntax().children().find_map(CurlyGroupWord::cast)
+    }
+}
+
+cst_node!(
+    CommandDefinition,
+    COMMAND_DEFINITION,
+    DOCUMENT_COMMAND_DEFINITION,
+    MATH_OPERATOR
+);
 
 impl CommandDefinition {
     pub fn command(&self) -> Option<SyntaxToken> {
@@ -555,9 +572,30 @@ impl CommandDefinition {
         self.syntax().children().find_map(CurlyGroupCommand::cast)
     }
 
-    pub fn implementation(&self) -> Option<CurlyGroup> {
+    pub fn parameter_count(&self) -> Option<BrackGroupWord> {
+        self.syntax().children().find_map(BrackGroupWord::cast)
+    }
+
+    pub fn default_argument(&self) -> Option<BrackGroup> {
+        self.syntax().children().find_map(BrackGroup::cast)
+    }
+
+    pub fn argument_spec(&self) -> Option<CurlyGroup> {
+        if self.syntax().kind() != DOCUMENT_COMMAND_DEFINITION {
+            return None;
+        }
+
         self.syntax().children().find_map(CurlyGroup::cast)
     }
+
+    pub fn implementation(&self) -> Option<CurlyGroup> {
+        let mut groups = self.syntax().children().filter_map(CurlyGroup::cast);
+        if self.syntax().kind() == DOCUMENT_COMMAND_DEFINITION {
+            groups.next();
+        }
+
+        groups.next()
+    }
 }
 
 cst_node!(AcronymReference, ACRONYM_REFERENCE);
@@ -566,6 +604,10 @@ impl AcronymReference {
     pub fn command(&self) -> Option<SyntaxToken> {
         self.syntax().first_token()
     }
+
+    pub fn name(&self) -> Option<CurlyGroupWord> {
+        self.syntax().children().find_map(CurlyGroupWord::cast)
+    }
 }
 
 cst_node!(AcronymDefinition, ACRONYM_DEFINITION);
@@ -627,6 +669,25 @@ impl ColorSetDefinition {
     }
 }
 
+cst_node!(ColorLetDefinition, COLOR_LET_DEFINITION);
+
+impl ColorLetDefinition {
+    pub fn command(&self) -> Option<SyntaxToken> {
+        self.syntax().first_token()
+    }
+
+    pub fn name(&self) -> Option<CurlyGroupWord> {
+        self.syntax().children().find_map(CurlyGroupWord::cast)
+    }
+
+    pub fn color(&self) -> Option<CurlyGroupWord> {
+        self.syntax()
+            .children()
+            .filter_map(CurlyGroupWord::cast)
+            .nth(1)
+    }
+}
+
 cst_node!(ColorReference, COLOR_REFERENCE);
 
 impl ColorReference {
@@ -49,6 +49,7 @@ pub enum SyntaxKind {
     LABEL_REFERENCE_RANGE_NAME,
     LABEL_NUMBER_NAME,
     COMMAND_DEFINITION_NAME,
+    DOCUMENT_COMMAND_DEFINITION_NAME,
     MATH_OPERATOR_NAME,
     GLOSSARY_ENTRY_DEFINITION_NAME,
     GLOSSARY_ENTRY_REFERENCE_NAME,
@@ -59,6 +60,7 @@ pub enum SyntaxKind {
     COLOR_REFERENCE_NAME,
     COLOR_DEFINITION_NAME,
     COLOR_SET_DEFINITION_NAME,
+    COLOR_LET_DEFINITION_NAME,
     TIKZ_LIBRARY_IMPORT_NAME,
     ENVIRONMENT_DEFINITION_NAME,
     BEGIN_BLOCK_COMMENT_NAME,
@@ -112,6 +114,7 @@ pub enum SyntaxKind {
     LABEL_REFERENCE_RANGE,
     LABEL_NUMBER,
     COMMAND_DEFINITION,
+    DOCUMENT_COMMAND_DEFINITION,
     MATH_OPERATOR,
     GLOSSARY_ENTRY_DEFINITION,
     GLOSSARY_ENTRY_REFERENCE,
@@ -122,6 +125,7 @@ pub enum SyntaxKind {
     COLOR_REFERENCE,
     COLOR_DEFINITION,
     COLOR_SET_DEFINITION,
+    COLOR_LET_DEFINITION,
     TIKZ_LIBRARY_IMPORT,
     ENVIRONMENT_DEFINITION,
     GRAPHICS_PATH,
@@ -164,6 +168,7 @@ impl SyntaxKind {
                 | LABEL_REFERENCE_RANGE_NAME
                 | LABEL_NUMBER_NAME
                 | COMMAND_DEFINITION_NAME
+                | DOCUMENT_COMMAND_DEFINITION_NAME
                 | MATH_OPERATOR_NAME
                 | GLOSSARY_ENTRY_DEFINITION_NAME
                 | GLOSSARY_ENTRY_REFERENCE_NAME
@@ -174,6 +179,7 @@ impl SyntaxKind {
                 | COLOR_REFERENCE_NAME
                 | COLOR_DEFINITION_NAME
                 | COLOR_SET_DEFINITION_NAME
+                | COLOR_LET_DEFINITION_NAME
                 | TIKZ_LIBRARY_IMPORT_NAME
         )
     }
@@ -0,0 +1,156 @@
+use std::path::PathBuf;
+
+use rustc_hash::FxHashMap;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum NodeKind {
+    VBox,
+    HBox,
+    VoidVBox,
+    VoidHBox,
+    Current,
+    Kern,
+    Glue,
+    Math,
+}
+
+impl NodeKind {
+    pub fn is_box(self) -> bool {
+        matches!(
+            self,
+            Self::VBox | Self::HBox | Self::VoidVBox | Self::VoidHBox
+        )
+    }
+}
+
+/// A record of a SyncTeX file which links a location in the output to a line of an input file.
+/// All coordinates are given in PDF points relative to the top left corner of the page.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Node {
+    pub kind: NodeKind,
+    pub tag: u32,
+    pub line: u32,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub depth: f64,
+}
+
+impl Node {
+    pub fn top(&self) -> f64 {
+        self.y - self.height
+    }
+
+    pub fn bottom(&self) -> f64 {
+        self.y + self.depth
+    }
+
+    pub fn left(&self) -> f64 {
+        self.x.min(self.x + self.width)
+    }
+
+    pub fn right(&self) -> f64 {
+        self.x.max(self.x + self.width)
+    }
+
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        self.left() <= x && x <= self.right() && self.top() <= y && y <= self.bottom()
+    }
+
+    fn area(&self) -> f64 {
+        (self.right() - self.left()) * (self.bottom() - self.top())
+    }
+
+    fn distance(&self, x: f64, y: f64) -> f64 {
+        let dx = (self.left() - x).max(x - self.right()).max(0.0);
+        let dy = (self.top() - y).max(y - self.bottom()).max(0.0);
+        dx.hypot(dy)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Page {
+    pub number: u32,
+    pub nodes: Vec<Node>,
+}
+
+/// A rectangle on a page of the output.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Area {
+    pub page: u32,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct SyncTex {
+    pub inputs: FxHashMap<u32, PathBuf>,
+    pub pages: Vec<Page>,
+}
+
+impl SyncTex {
+    /// Finds the area of the output which belongs to the given line (starting at 1) of an input file.
+    /// If there is no output for the line itself, the closest line with output is used instead.
+    pub fn forward(&self, tag: u32, line: u32) -> Option<Area> {
+        let nodes = || {
+            self.pages.iter().flat_map(|page| {
+                page.nodes
+                    .iter()
+                    .filter(|node| node.tag == tag)
+                    .map(move |node| (page, node))
+            })
+        };
+
+        let best_line = nodes()
+            .map(|(_, node)| node.line)
+            .min_by_key(|&node_line| (node_line.abs_diff(line), node_line < line))?;
+
+        let (page, _) = nodes().find(|(_, node)| node.line == best_line)?;
+        let matches: Vec<_> = page
+            .nodes
+            .iter()
+            .filter(|node| node.tag == tag && node.line == best_line)
+            .collect();
+
+        // Vertical boxes tend to span the whole page, so they are only used as a last resort.
+        let boxes = [NodeKind::HBox, NodeKind::VoidHBox, NodeKind::VoidVBox]
+            .into_iter()
+            .map(|kind| {
+                matches
+                    .iter()
+                    .copied()
+                    .filter(|node| node.kind == kind)
+                    .collect::<Vec<_>>()
+            })
+            .find(|nodes| !nodes.is_empty())
+            .unwrap_or_else(|| matches.clone());
+
+        let left = boxes.iter().map(|node| node.left()).reduce(f64::min)?;
+        let right = boxes.iter().map(|node| node.right()).reduce(f64::max)?;
+        let top = boxes.iter().map(|node| node.top()).reduce(f64::min)?;
+        let bottom = boxes.iter().map(|node| node.bottom()).reduce(f64::max)?;
+        Some(Area {
+            page: page.number,
+            x: left,
+            y: top,
+            width: right - left,
+            height: bottom - top,
+        })
+    }
+
+    /// Finds the node which belongs to the given point of a page.
+    /// The smallest box containing the point wins; otherwise the closest node is used.
+    pub fn inverse(&self, page: u32, x: f64, y: f64) -> Option<&Node> {
+        let page = self.pages.iter().find(|p| p.number == page)?;
+        let nodes = page.nodes.iter().filter(|node| node.line > 0);
+
+        nodes
+            .clone()
+            .filter(|node| node.kind.is_box() && node.contains(x, y))
+            .min_by(|a, b| a.area().total_cmp(&b.area()))
+            .or_else(|| nodes.min_by(|a, b| a.distance(x, y).total_cmp(&b.distance(x, y))))
+    }
+}
@@ -1,5 +1,6 @@
 mod client;
 mod fixture;
 mod issues;
+mod synctex;
 mod text_document;
 mod workspace;
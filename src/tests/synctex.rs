@@ -0,0 +1,141 @@
+use lsp_types::{
+    ClientCapabilities, Location, Position, Range, TextDocumentIdentifier,
+    TextDocumentPositionParams,
+};
+
+use crate::{
+    features::synctex::{ForwardSearchPosition, InverseSearchParams},
+    tests::client::Client,
+};
+
+struct ForwardSearchPositionRequest;
+
+impl lsp_types::request::Request for ForwardSearchPositionRequest {
+    type Params = TextDocumentPositionParams;
+
+    type Result = Option<ForwardSearchPosition>;
+
+    const METHOD: &'static str = "texlab/forwardSearchPosition";
+}
+
+struct InverseSearchRequest;
+
+impl lsp_types::request::Request for InverseSearchRequest {
+    type Params = InverseSearchParams;
+
+    type Result = Option<Location>;
+
+    const METHOD: &'static str = "texlab/inverseSearch";
+}
+
+const MAIN: &str = r#"\documentclass{article}
+\begin{document}
+Hello World!
+
+Second paragraph.
+\end{document}
+"#;
+
+/// Laid out like the output of `pdflatex -synctex=-1` for `MAIN`.
+const SYNCTEX: &str = r#"SyncTeX Version:1
+Input:1:{dir}/main.tex
+Input:2:/usr/share/texmf-dist/tex/latex/base/article.cls
+Input:3:/usr/share/texmf-dist/tex/latex/base/size10.clo
+Input:4:{dir}/main.aux
+Output:pdf
+Magnification:1000
+Unit:1
+X Offset:0
+Y Offset:0
+Content:
+!436
+{1
+[1,6:4736286,49149672:22609920,43868160,0
+(1,3:4736286,6850867:22609920,455111,127431
+g1,3:5716522,6850867
+x1,3:5716522,6850867
+k1,3:7894946,6850867:-40050
+g1,3:8176574,6850867
+)
+(1,5:4736286,8424039:22609920,455111,0
+g1,5:5716522,8424039
+x1,5:5716522,8424039
+$1,5:6873108,8424039
+)
+]
+!298
+}1
+Postamble:
+Count:12
+!35
+Post scriptum:
+"#;
+
+fn spawn(synctex: &str) -> Client {
+    let mut client = Client::spawn();
+    client.initialize(ClientCapabilities::default(), None);
+    client.open("main.tex", "latex", MAIN.to_string());
+
+    let dir = client.uri("main.tex").to_file_path().unwrap();
+    let dir = dir.parent().unwrap();
+    let text = synctex.replace("{dir}", dir.to_str().unwrap());
+    std::fs::write(dir.join("main.synctex"), text).unwrap();
+    client
+}
+
+#[test]
+fn forward_search_position() {
+    let mut client = spawn(SYNCTEX);
+    let actual = client
+        .request::<ForwardSearchPositionRequest>(TextDocumentPositionParams::new(
+            TextDocumentIdentifier::new(client.uri("main.tex")),
+            Position::new(2, 3),
+        ))
+        .unwrap()
+        .unwrap();
+
+    client.shutdown();
+    assert_eq!(actual.page, 1);
+    for (a, b) in [
+        (actual.x, 72.0),
+        (actual.y, 97.23),
+        (actual.width, 343.71),
+        (actual.height, 8.86),
+    ] {
+        assert!((a - b).abs() < 0.01, "{actual:?}");
+    }
+}
+
+#[test]
+fn forward_search_position_missing_file() {
+    let mut client = Client::spawn();
+    client.initialize(ClientCapabilities::default(), None);
+    client.open("main.tex", "latex", MAIN.to_string());
+    let actual = client
+        .request::<ForwardSearchPositionRequest>(TextDocumentPositionParams::new(
+            TextDocumentIdentifier::new(client.uri("main.tex")),
+            Position::new(2, 3),
+        ))
+        .unwrap();
+
+    client.shutdown();
+    assert_eq!(actual, None);
+}
+
+#[test]
+fn inverse_search() {
+    let mut client = spawn(SYNCTEX);
+    let actual = client
+        .request::<InverseSearchRequest>(InverseSearchParams {
+            text_document: TextDocumentIdentifier::new(client.uri("main.tex")),
+            page: 1,
+            x: 150.0,
+            y: 125.0,
+        })
+        .unwrap();
+
+    let position = Position::new(4, 0);
+    let expected = Location::new(client.uri("main.tex"), Range::new(position, position));
+    client.shutdown();
+    assert_eq!(actual, Some(expected));
+}
@@ -1,6 +1,9 @@
+mod code_action;
 mod completion;
 mod definition;
+mod diagnostics;
 mod did_change_configuration;
+mod document_color;
 mod document_highlight;
 mod document_link;
 mod document_symbol;
@@ -8,5 +11,9 @@ mod folding_range;
 mod formatting;
 mod hover;
 mod inlay_hint;
+mod linked_editing_range;
 mod references;
 mod rename;
+mod selection_range;
+mod semantic_tokens;
+mod signature_help;
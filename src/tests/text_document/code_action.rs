@@ -0,0 +1,131 @@
+use lsp_types::{
+    request::CodeActionRequest, ClientCapabilities, CodeActionContext, CodeActionOrCommand,
+    CodeActionParams, Range, TextDocumentIdentifier, TextEdit,
+};
+
+use crate::tests::{client::Client, fixture};
+
+fn check(fixture: &str, new_text: &str) {
+    let mut client = Client::spawn();
+    client.initialize(ClientCapabilities::default(), None);
+
+    let fixture = fixture::parse(fixture);
+    let file_name = fixture.files[0].name;
+    for file in fixture.files {
+        client.open(file.name, file.lang, file.text);
+    }
+
+    let uri = client.uri(file_name);
+    let range = fixture.ranges.get(&1).map_or_else(
+        || {
+            let position = fixture.cursor.unwrap().position;
+            Range::new(position, position)
+        },
+        |ranges| ranges[&1].range,
+    );
+
+    let edit_range = fixture
+        .ranges
+        .get(&2)
+        .map_or(range, |ranges| ranges[&1].range);
+
+    let expected_edits = vec![TextEdit::new(edit_range, new_text.into())];
+
+    let actions = client
+        .request::<CodeActionRequest>(CodeActionParams {
+            text_document: TextDocumentIdentifier::new(uri.clone()),
+            range,
+            context: CodeActionContext::default(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+        .unwrap()
+        .unwrap_or_default();
+
+    client.shutdown();
+
+    assert_eq!(actions.len(), 1);
+    let action = match &actions[0] {
+        CodeActionOrCommand::CodeAction(action) => action,
+        CodeActionOrCommand::Command(_) => unreachable!(),
+    };
+
+    let mut changes = action.edit.clone().unwrap().changes.unwrap();
+    assert_eq!(changes.remove(&uri), Some(expected_edits));
+}
+
+#[test]
+fn tex_mismatched_environment() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \begin{foo}
+%SRC \end{bar}
+%1.1      ^^^
+"#,
+        "foo",
+    );
+}
+
+#[test]
+fn tex_mismatched_environment_begin() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \begin{foo}
+%1.1        ^^^
+%SRC \end{bar}
+%2.1      ^^^
+"#,
+        "foo",
+    );
+}
+
+#[test]
+fn tex_missing_rcurly() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \label{foo
+%SRC \ref{bar}
+%CUR ^
+"#,
+        "}",
+    );
+}
+
+#[test]
+fn tex_unexpected_rcurly() {
+    check(
+        r#"
+%TEX main.tex
+%SRC foo}
+%1.1    ^
+"#,
+        "",
+    );
+}
+
+#[test]
+fn bib_missing_eq() {
+    check(
+        r#"
+%BIB main.bib
+%SRC @article{foo, author {bar}}
+%CUR                     ^
+"#,
+        " =",
+    );
+}
+
+#[test]
+fn bib_missing_lcurly() {
+    check(
+        r#"
+%BIB main.bib
+%SRC @article foo,}
+%CUR         ^
+"#,
+        "{",
+    );
+}
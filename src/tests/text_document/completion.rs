@@ -649,6 +649,27 @@ fn import_class_closed_brace() {
     ));
 }
 
+#[test]
+fn key_value_package_option() {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \usepackage[landsc]{geometry}
+%CUR                   ^
+%1.1             ^^^^^^"#
+    ));
+}
+
+#[test]
+fn key_value_command_value() {
+    assert_items!(complete(
+        r#"
+%TEX main.tex
+%SRC \hypersetup{colorlinks=}
+%CUR                        ^"#
+    ));
+}
+
 #[test]
 fn label() {
     assert_items!(complete(
@@ -713,6 +734,48 @@ fn label() {
     ));
 }
 
+#[test]
+fn label_eqref_prefers_equations() {
+    let labels: Vec<_> = complete(
+        r#"
+%TEX main.tex
+%SRC \section{Foo}
+%SRC \label{sec:foo}
+%SRC \begin{equation}
+%SRC \label{eq:foo}
+%SRC \end{equation}
+%SRC \eqref{}
+%CUR        ^"#,
+    )
+    .into_iter()
+    .map(|item| item.label)
+    .collect();
+
+    assert_eq!(labels, vec!["eq:foo", "sec:foo"]);
+}
+
+#[test]
+fn label_cref_detail() {
+    let details: Vec<_> = complete(
+        r#"
+%TEX main.tex
+%SRC \begin{figure}
+%SRC \caption{Baz}
+%SRC \label{fig:baz}
+%SRC \end{figure}
+%SRC \cref{}
+%CUR       ^
+
+%TEX main.aux
+%SRC \newlabel{fig:baz}{{3}{1}}"#,
+    )
+    .into_iter()
+    .map(|item| item.detail)
+    .collect();
+
+    assert_eq!(details, vec![Some(String::from("Figure 3"))]);
+}
+
 #[test]
 fn theorem_begin() {
     assert_items!(complete(
@@ -138,3 +138,37 @@ fn string_field() {
 "#,
     )
 }
+
+#[test]
+fn environment_definition() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \newtheorem{foo}{Foo}
+%1.3             ^^^
+%1.2 ^^^^^^^^^^^^^^^^^^^^^
+%SRC \begin{foo}
+%CUR         ^
+%1.1        ^^^
+%SRC \end{foo}
+"#,
+    )
+}
+
+#[test]
+fn label_magic_root() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \label{foo}
+%1.3        ^^^
+%1.2        ^^^
+
+%TEX chapter.tex
+%SRC % !TEX root = main.tex
+%SRC \ref{foo}
+%CUR       ^
+%1.1      ^^^
+"#,
+    )
+}
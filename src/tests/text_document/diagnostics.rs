@@ -0,0 +1,490 @@
+use std::path::Path;
+
+use assert_unordered::assert_eq_unordered;
+use lsp_types::{Location, NumberOrString, Url};
+
+use crate::{
+    db::{diagnostics::collect_filtered, Language, Owner, Workspace},
+    tests::fixture,
+    Database, DiagnosticsOptions, Options,
+};
+
+fn uri(name: &str) -> Url {
+    Url::parse("file:///texlab/").unwrap().join(name).unwrap()
+}
+
+/// Diagnostics are published with a delay, so they are collected from the database directly.
+fn find_diagnostics(
+    fixture: &fixture::Fixture,
+    options: Options,
+) -> Vec<(Url, lsp_types::Diagnostic)> {
+    let mut db = Database::default();
+    let workspace = Workspace::get(&db);
+    workspace.set_options(&mut db).to(options);
+    for file in &fixture.files {
+        let language = Language::from_id(file.lang)
+            .or_else(|| Language::from_path(Path::new(file.name)))
+            .unwrap();
+        let text = file.text.clone();
+        workspace.open(&mut db, uri(file.name), text, language, Owner::Client);
+    }
+
+    collect_filtered(&db, workspace)
+        .into_iter()
+        .flat_map(|(document, diagnostics)| {
+            let uri = document.location(&db).uri(&db).clone();
+            diagnostics
+                .into_iter()
+                .map(move |diagnostic| (uri.clone(), diagnostic))
+        })
+        .collect()
+}
+
+fn check_with_options(fixture: &str, code: i32, options: Options) {
+    let fixture = fixture::parse(fixture);
+
+    let mut expected_locations = Vec::new();
+    for ranges in fixture.ranges.values() {
+        expected_locations.push(Location::new(uri(ranges[&1].name), ranges[&1].range));
+    }
+
+    let code = Some(NumberOrString::Number(code));
+    let actual_locations: Vec<_> = find_diagnostics(&fixture, options)
+        .into_iter()
+        .filter(|(_, diagnostic)| diagnostic.code == code)
+        .map(|(uri, diagnostic)| Location::new(uri, diagnostic.range))
+        .collect();
+
+    assert_eq_unordered!(actual_locations, expected_locations);
+}
+
+fn check(fixture: &str, code: i32) {
+    check_with_options(fixture, code, Options::default());
+}
+
+#[test]
+fn bib_expecting_eq() {
+    check(
+        r#"
+%BIB main.bib
+%SRC @article{foo, author {bar}}
+%1.1               ^^^^^^
+"#,
+        7,
+    );
+}
+
+#[test]
+fn bib_field_with_eq() {
+    check(
+        r#"
+%BIB main.bib
+%SRC @article{foo, author = {bar}}
+"#,
+        7,
+    );
+}
+
+#[test]
+fn label_undefined() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \label{foo}
+%SRC \ref{foo}
+%SRC \ref{bar}
+%1.1      ^^^
+"#,
+        9,
+    );
+}
+
+#[test]
+fn label_defined_in_child() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \input{child}
+%SRC \ref{foo}
+
+%TEX child.tex
+%SRC \label{foo}
+"#,
+        9,
+    );
+}
+
+#[test]
+fn label_duplicate() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \input{child}
+%SRC \label{foo}
+%1.1        ^^^
+
+%TEX child.tex
+%SRC \label{foo}
+%2.1        ^^^
+%SRC \label{bar}
+"#,
+        10,
+    );
+}
+
+#[test]
+fn label_duplicate_related_information() {
+    let fixture = fixture::parse(
+        r#"
+%TEX main.tex
+%SRC \input{child}
+%SRC \label{foo}
+
+%TEX child.tex
+%SRC \label{foo}
+%1.1        ^^^
+"#,
+    );
+
+    let code = Some(NumberOrString::Number(10));
+    let related_information = find_diagnostics(&fixture, Options::default())
+        .into_iter()
+        .find(|(uri, diagnostic)| *uri == self::uri("main.tex") && diagnostic.code == code)
+        .and_then(|(_, diagnostic)| diagnostic.related_information)
+        .unwrap();
+
+    let locations: Vec<_> = related_information
+        .into_iter()
+        .map(|information| information.location)
+        .collect();
+
+    let range = fixture.ranges[&1][&1].range;
+    assert_eq!(locations, vec![Location::new(uri("child.tex"), range)]);
+}
+
+#[test]
+fn label_unused() {
+    check_with_options(
+        r#"
+%TEX main.tex
+%SRC \label{foo}
+%1.1        ^^^
+%SRC \label{bar}
+%SRC \ref{bar}
+"#,
+        11,
+        Options {
+            diagnostics: DiagnosticsOptions {
+                unused_labels: true,
+                ..DiagnosticsOptions::default()
+            },
+            ..Options::default()
+        },
+    );
+}
+
+#[test]
+fn label_unused_disabled() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \label{foo}
+"#,
+        11,
+    );
+}
+
+#[test]
+fn citation_undefined() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \addbibresource{main.bib}
+%SRC \cite{foo}
+%SRC \cite{bar}
+%1.1       ^^^
+
+%BIB main.bib
+%SRC @article{foo,}
+"#,
+        12,
+    );
+}
+
+#[test]
+fn citation_without_bibliography() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \cite{foo}
+"#,
+        12,
+    );
+}
+
+#[test]
+fn entry_unused() {
+    check_with_options(
+        r#"
+%TEX main.tex
+%SRC \addbibresource{main.bib}
+%SRC \cite{foo}
+
+%BIB main.bib
+%SRC @article{foo,}
+%SRC @article{bar,}
+%1.1          ^^^
+"#,
+        13,
+        Options {
+            diagnostics: DiagnosticsOptions {
+                unused_entries: true,
+                ..DiagnosticsOptions::default()
+            },
+            ..Options::default()
+        },
+    );
+}
+
+#[test]
+fn entry_unused_nocite() {
+    check_with_options(
+        r#"
+%TEX main.tex
+%SRC \addbibresource{main.bib}
+%SRC \nocite{*}
+
+%BIB main.bib
+%SRC @article{foo,}
+"#,
+        13,
+        Options {
+            diagnostics: DiagnosticsOptions {
+                unused_entries: true,
+                ..DiagnosticsOptions::default()
+            },
+            ..Options::default()
+        },
+    );
+}
+
+#[test]
+fn bib_unknown_entry_type() {
+    check(
+        r#"
+%BIB main.bib
+%SRC @foo{bar, title = {Baz}}
+%1.1 ^^^^
+"#,
+        14,
+    );
+}
+
+#[test]
+fn bib_missing_required_field() {
+    check(
+        r#"
+%BIB main.bib
+%SRC @article{foo, author = {Bar}, title = {Baz}, journaltitle = {Qux}}
+%1.1          ^^^
+"#,
+        16,
+    );
+}
+
+#[test]
+fn bib_missing_required_field_crossref() {
+    check(
+        r#"
+%BIB main.bib
+%SRC @inproceedings{foo, author = {Bar}, title = {Baz}, crossref = {qux}}
+"#,
+        16,
+    );
+}
+
+#[test]
+fn bib_unknown_field() {
+    check_with_options(
+        r#"
+%BIB main.bib
+%SRC @article{foo, author = {Bar}, title = {Baz}, journal = {Qux}, year = {2023}, foo = {bar}}
+%1.1                                                                              ^^^
+"#,
+        15,
+        Options {
+            diagnostics: DiagnosticsOptions {
+                unknown_fields: true,
+                ..DiagnosticsOptions::default()
+            },
+            ..Options::default()
+        },
+    );
+}
+
+#[test]
+fn bib_unknown_field_exporter() {
+    check_with_options(
+        r#"
+%BIB main.bib
+%SRC @article{foo, title = {Bar}, timestamp = {2023}, biburl = {Baz}, bibsource = {Qux}}
+"#,
+        15,
+        Options {
+            diagnostics: DiagnosticsOptions {
+                unknown_fields: true,
+                ..DiagnosticsOptions::default()
+            },
+            ..Options::default()
+        },
+    );
+}
+
+#[test]
+fn bib_unknown_field_disabled() {
+    check(
+        r#"
+%BIB main.bib
+%SRC @article{foo, foo = {bar}}
+"#,
+        15,
+    );
+}
+
+#[test]
+fn entry_duplicate_ignore_case() {
+    check(
+        r#"
+%BIB main.bib
+%SRC @article{foo,}
+%1.1          ^^^
+%SRC @article{Foo,}
+%2.1          ^^^
+"#,
+        17,
+    );
+}
+
+#[test]
+fn entry_duplicate_related_bibliographies() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \addbibresource{a.bib}
+%SRC \addbibresource{b.bib}
+
+%BIB a.bib
+%SRC @article{foo,}
+%1.1          ^^^
+
+%BIB b.bib
+%SRC @article{foo,}
+%2.1          ^^^
+"#,
+        17,
+    );
+}
+
+#[test]
+fn entry_duplicate_unrelated_bibliographies() {
+    check(
+        r#"
+%BIB a.bib
+%SRC @article{foo,}
+
+%BIB b.bib
+%SRC @article{foo,}
+"#,
+        17,
+    );
+}
+
+#[test]
+fn entry_likely_duplicate_doi() {
+    check_with_options(
+        r#"
+%BIB main.bib
+%SRC @article{foo, doi = {10.1000/182}}
+%1.1          ^^^
+%SRC @article{bar, doi = {https://doi.org/10.1000/182}}
+%2.1          ^^^
+%SRC @article{baz, doi = {10.1000/183}}
+"#,
+        18,
+        Options {
+            diagnostics: DiagnosticsOptions {
+                likely_duplicate_entries: true,
+                ..DiagnosticsOptions::default()
+            },
+            ..Options::default()
+        },
+    );
+}
+
+#[test]
+fn entry_likely_duplicate_title_year() {
+    check_with_options(
+        r#"
+%BIB main.bib
+%SRC @article{foo, title = {The {TeX}book}, year = {1984}}
+%1.1          ^^^
+%SRC @book{bar, title = {The TeXbook}, date = {1984-01-01}}
+%2.1       ^^^
+%SRC @book{baz, title = {The TeXbook}, year = {1986}}
+"#,
+        18,
+        Options {
+            diagnostics: DiagnosticsOptions {
+                likely_duplicate_entries: true,
+                ..DiagnosticsOptions::default()
+            },
+            ..Options::default()
+        },
+    );
+}
+
+#[test]
+fn entry_likely_duplicate_disabled() {
+    check(
+        r#"
+%BIB main.bib
+%SRC @article{foo, doi = {10.1000/182}}
+%SRC @article{bar, doi = {10.1000/182}}
+"#,
+        18,
+    );
+}
+
+#[test]
+fn blg_unknown_bibliography() {
+    let fixture = fixture::parse(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \bibliography{main}
+
+%BIB main.bib
+%SRC @article{foo,}
+
+%LOG main.blg
+%SRC Warning--empty journal in foo
+%SRC --line 1 of file main.bib
+%SRC Warning--empty journal in bar
+%SRC --line 1 of file other.bib
+"#,
+    );
+
+    let actual_locations: Vec<_> = find_diagnostics(&fixture, Options::default())
+        .into_iter()
+        .filter(|(_, diagnostic)| diagnostic.source.as_deref() == Some("latex-build"))
+        .map(|(uri, diagnostic)| Location::new(uri, diagnostic.range))
+        .collect();
+
+    let position = lsp_types::Position::new(0, 0);
+    let expected_locations = vec![Location::new(
+        uri("main.bib"),
+        lsp_types::Range::new(position, position),
+    )];
+
+    assert_eq!(actual_locations, expected_locations);
+}
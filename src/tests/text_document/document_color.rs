@@ -0,0 +1,155 @@
+use lsp_types::{
+    request::{ColorPresentationRequest, DocumentColor},
+    ClientCapabilities, Color, ColorInformation, ColorPresentation, ColorPresentationParams,
+    DocumentColorParams, TextDocumentIdentifier, TextEdit,
+};
+
+use crate::tests::{client::Client, fixture};
+
+fn rgb(red: f32, green: f32, blue: f32) -> Color {
+    Color {
+        red,
+        green,
+        blue,
+        alpha: 1.0,
+    }
+}
+
+fn check_colors(fixture: &str, expected_colors: Vec<Color>) {
+    let mut client = Client::spawn();
+    client.initialize(ClientCapabilities::default(), None);
+
+    let fixture = fixture::parse(fixture);
+    let uri = client.uri(fixture.files[0].name);
+    for file in fixture.files {
+        client.open(file.name, file.lang, file.text);
+    }
+
+    let actual_colors = client
+        .request::<DocumentColor>(DocumentColorParams {
+            text_document: TextDocumentIdentifier::new(uri),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+        .unwrap();
+
+    client.shutdown();
+
+    let expected_colors: Vec<_> = fixture
+        .ranges
+        .values()
+        .map(|ranges| ranges[&1].range)
+        .zip(expected_colors)
+        .map(|(range, color)| ColorInformation { range, color })
+        .collect();
+
+    assert_eq!(actual_colors, expected_colors);
+}
+
+fn check_presentation(fixture: &str, color: Color, expected_text: &str) {
+    let mut client = Client::spawn();
+    client.initialize(ClientCapabilities::default(), None);
+
+    let fixture = fixture::parse(fixture);
+    let uri = client.uri(fixture.files[0].name);
+    for file in fixture.files {
+        client.open(file.name, file.lang, file.text);
+    }
+
+    let range = fixture.ranges[&1][&1].range;
+    let actual_presentations = client
+        .request::<ColorPresentationRequest>(ColorPresentationParams {
+            text_document: TextDocumentIdentifier::new(uri),
+            color,
+            range,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+        .unwrap();
+
+    client.shutdown();
+
+    let expected_presentations = vec![ColorPresentation {
+        label: expected_text.into(),
+        text_edit: Some(TextEdit::new(range, expected_text.into())),
+        additional_text_edits: None,
+    }];
+
+    assert_eq!(actual_presentations, expected_presentations);
+}
+
+#[test]
+fn definition() {
+    check_colors(
+        r#"
+%TEX main.tex
+%SRC \definecolor{foo}{rgb}{1,0.5,0}
+%1.1                        ^^^^^^^
+%SRC \definecolor{bar}{HTML}{0000FF}
+%2.1                         ^^^^^^
+%SRC \definecolor{baz}{gray}{0}
+%3.1                         ^
+"#,
+        vec![rgb(1.0, 0.5, 0.0), rgb(0.0, 0.0, 1.0), rgb(0.0, 0.0, 0.0)],
+    );
+}
+
+#[test]
+fn reference() {
+    check_colors(
+        r#"
+%TEX main.tex
+%SRC \definecolor{foo}{RGB}{0,255,0}
+%1.1                        ^^^^^^^
+%SRC \color{foo}\color{red}\color{Cyan}\color{qux}
+%2.1        ^^^
+%3.1                   ^^^
+%4.1                              ^^^^
+"#,
+        vec![
+            rgb(0.0, 1.0, 0.0),
+            rgb(0.0, 1.0, 0.0),
+            rgb(1.0, 0.0, 0.0),
+            rgb(0.0, 1.0, 1.0),
+        ],
+    );
+}
+
+#[test]
+fn presentation_rgb() {
+    check_presentation(
+        r#"
+%TEX main.tex
+%SRC \definecolor{foo}{rgb}{1,0.5,0}
+%1.1                        ^^^^^^^
+"#,
+        rgb(0.25, 0.5, 1.0),
+        "0.25,0.5,1",
+    );
+}
+
+#[test]
+fn presentation_html() {
+    check_presentation(
+        r#"
+%TEX main.tex
+%SRC \definecolor{foo}{HTML}{0000FF}
+%1.1                         ^^^^^^
+"#,
+        rgb(1.0, 0.5, 0.0),
+        "FF8000",
+    );
+}
+
+#[test]
+fn presentation_cmyk() {
+    check_presentation(
+        r#"
+%TEX main.tex
+%SRC \definecolor{foo}{cmyk}{0,0,0,1}
+%1.1                         ^^^^^^^
+"#,
+        rgb(0.0, 0.5, 0.5),
+        "0.5,0,0,0.5",
+    );
+}
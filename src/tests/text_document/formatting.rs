@@ -1,6 +1,7 @@
 use insta::assert_snapshot;
 use lsp_types::{
-    request::Formatting, ClientCapabilities, DocumentFormattingParams, FormattingOptions,
+    notification::DidChangeConfiguration, request::Formatting, ClientCapabilities,
+    DidChangeConfigurationParams, DocumentFormattingParams, FormattingOptions,
     TextDocumentIdentifier,
 };
 
@@ -10,8 +11,17 @@ use crate::{
 };
 
 fn format(fixture: &str) -> String {
+    format_with_settings(fixture, serde_json::json!({}))
+}
+
+fn format_latex(fixture: &str) -> String {
+    format_with_settings(fixture, serde_json::json!({ "latexFormatter": "texlab" }))
+}
+
+fn format_with_settings(fixture: &str, settings: serde_json::Value) -> String {
     let mut client = Client::spawn();
     client.initialize(ClientCapabilities::default(), None);
+    client.notify::<DidChangeConfiguration>(DidChangeConfigurationParams { settings });
 
     let fixture = fixture::parse(fixture);
     let file = fixture.files.into_iter().next().unwrap();
@@ -130,3 +140,89 @@ fn bibtex_internal_preamble() {
 %SRC     "foo bar baz" }"#,
     ));
 }
+
+#[test]
+fn latex_internal_indent_environments() {
+    assert_snapshot!(format_latex(
+        r#"
+%TEX main.tex
+%SRC \begin{document}
+%SRC \begin{itemize}
+%SRC \item foo
+%SRC bar
+%SRC   \item baz
+%SRC \end{itemize}
+%SRC \end{document}"#,
+    ));
+}
+
+#[test]
+fn latex_internal_normalize_whitespace() {
+    assert_snapshot!(format_latex(
+        r#"
+%TEX main.tex
+%SRC \section {Foo}   bar   baz   "#,
+    ));
+}
+
+#[test]
+fn latex_internal_verbatim_and_comments() {
+    assert_snapshot!(format_latex(
+        r#"
+%TEX main.tex
+%SRC \begin{itemize}
+%SRC \item foo   % a   comment
+%SRC \end{itemize}
+%SRC \begin{verbatim}
+%SRC   x    y
+%SRC \end{verbatim}"#,
+    ));
+}
+
+#[test]
+fn latex_internal_verbatim_inline() {
+    assert_snapshot!(format_latex(
+        r#"
+%TEX main.tex
+%SRC \verb|a   b|  \verb*|c  d|
+%SRC \lstinline[language=C]{x  =  {1};}
+%SRC \mintinline{python}|y  =  2|"#,
+    ));
+}
+
+#[test]
+fn latex_internal_verbatim_end() {
+    assert_snapshot!(format_latex(
+        r#"
+%TEX main.tex
+%SRC \begin{itemize}
+%SRC \item foo
+%SRC \begin{verbatim}
+%SRC   x
+%SRC   \end{verbatim}
+%SRC \end{itemize}"#,
+    ));
+}
+
+#[test]
+fn latex_internal_comment_lines() {
+    assert_snapshot!(format_latex(
+        r#"
+%TEX main.tex
+%SRC \begin{itemize}
+%SRC % first
+%SRC       % second
+%SRC \item foo
+%SRC \end{itemize}"#,
+    ));
+}
+
+#[test]
+fn latex_internal_line_length() {
+    assert_snapshot!(format_with_settings(
+        r#"
+%TEX main.tex
+%SRC foo bar baz qux quux corge"#,
+        serde_json::json!({ "latexFormatter": "texlab", "formatterLineLength": 20 }),
+    ));
+}
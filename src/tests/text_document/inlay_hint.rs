@@ -63,3 +63,60 @@ fn label_definition() {
 "#,
     ));
 }
+
+#[test]
+fn label_reference() {
+    assert_json_snapshot!(check(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \begin{document}
+%SRC \section{Foo}\label{sec:foo}
+%SRC See \ref{sec:foo} and \cref{fig:qux}.
+%SRC \begin{figure}
+%SRC     \caption{Qux}
+%SRC     \label{fig:qux}
+%SRC \end{figure}
+%SRC \end{document}
+
+%TEX main.aux
+%SRC \newlabel{sec:foo}{{1}{1}}
+%SRC \newlabel{fig:qux}{{1}{1}}
+"#,
+    ));
+}
+
+#[test]
+fn label_reference_without_number() {
+    let actual_hints = check(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \begin{document}
+%SRC \section{Foo}\label{sec:foo}
+%SRC See \ref{sec:foo}.
+%SRC \end{document}
+"#,
+    );
+
+    let lines: Vec<_> = actual_hints.iter().map(|hint| hint.position.line).collect();
+    assert_eq!(lines, vec![2], "{actual_hints:?}");
+}
+
+#[test]
+fn citation() {
+    assert_json_snapshot!(check(
+        r#"
+%TEX main.tex
+%SRC \documentclass{article}
+%SRC \addbibresource{main.bib}
+%SRC \begin{document}
+%SRC \cite{rsa} and \cite[p.~3]{knuth,foo}
+%SRC \end{document}
+
+%BIB main.bib
+%SRC @article{rsa, author = {Rivest, R. L. and Shamir, A. and Adleman, L.}, year = {1978}}
+%SRC @book{knuth, author = {Donald E. Knuth}, year = {1984}}
+"#,
+    ));
+}
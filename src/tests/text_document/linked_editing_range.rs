@@ -0,0 +1,71 @@
+use lsp_types::{
+    request::LinkedEditingRange, ClientCapabilities, LinkedEditingRangeParams, LinkedEditingRanges,
+};
+
+use crate::tests::{client::Client, fixture};
+
+fn check(fixture: &str) {
+    let mut client = Client::spawn();
+    client.initialize(ClientCapabilities::default(), None);
+
+    let fixture = fixture::parse(fixture);
+    for file in fixture.files {
+        client.open(file.name, file.lang, file.text);
+    }
+
+    let actual_ranges = client
+        .request::<LinkedEditingRange>(LinkedEditingRangeParams {
+            text_document_position_params: fixture.cursor.unwrap().into_params(&client),
+            work_done_progress_params: Default::default(),
+        })
+        .unwrap();
+
+    client.shutdown();
+
+    let expected_ranges = fixture.ranges.get(&1).map(|ranges| LinkedEditingRanges {
+        ranges: vec![ranges[&1].range, ranges[&2].range],
+        word_pattern: None,
+    });
+
+    assert_eq!(actual_ranges, expected_ranges);
+}
+
+#[test]
+fn begin() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \begin{foo}
+%CUR         ^
+%1.1        ^^^
+%SRC \end{foo}
+%1.2      ^^^
+"#,
+    );
+}
+
+#[test]
+fn end() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \begin{foo}
+%1.1        ^^^
+%SRC \end{foo}
+%CUR        ^
+%1.2      ^^^
+"#,
+    );
+}
+
+#[test]
+fn mismatched() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \begin{foo}
+%CUR         ^
+%SRC \end{bar}
+"#,
+    );
+}
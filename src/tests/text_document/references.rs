@@ -269,3 +269,43 @@ fn string_definition_include_decl() {
         },
     )
 }
+
+#[test]
+fn command_include_decl() {
+    check(
+        r#"
+%TEX foo.tex
+%SRC \newcommand{\foo}{foo}
+%1.1             ^^^^
+%SRC \foo
+%CUR  ^
+%2.1 ^^^^
+%SRC \include{bar.tex}
+
+%TEX bar.tex
+%SRC \foo
+%3.1 ^^^^
+"#,
+        ReferenceContext {
+            include_declaration: true,
+        },
+    )
+}
+
+#[test]
+fn environment_include_decl() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \newenvironment{foo}{}{}
+%1.1                 ^^^
+%SRC \begin{foo}
+%CUR         ^
+%2.1        ^^^
+%SRC \end{foo}
+"#,
+        ReferenceContext {
+            include_declaration: true,
+        },
+    )
+}
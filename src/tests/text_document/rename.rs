@@ -108,3 +108,80 @@ fn label() {
         "bar",
     )
 }
+
+#[test]
+fn environment() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \newenvironment{foo}{}{}
+%1.1                 ^^^
+%SRC \begin{foo}
+%CUR         ^
+%2.1        ^^^
+%SRC \end{foo}
+%3.1      ^^^
+"#,
+        "qux",
+    )
+}
+
+#[test]
+fn glossary_entry() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \newglossaryentry{foo}{name={foo}, description={bar}}
+%1.1                   ^^^
+%SRC \gls{foo}
+%CUR       ^
+%2.1      ^^^
+"#,
+        "qux",
+    )
+}
+
+#[test]
+fn color() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \definecolor{foo}{rgb}{1,1,1}
+%1.1              ^^^
+%SRC \color{foo}
+%CUR         ^
+%2.1        ^^^
+"#,
+        "qux",
+    )
+}
+
+#[test]
+fn color_let() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \colorlet{foo}{red!50}
+%1.1           ^^^
+%SRC \color{foo}
+%CUR         ^
+%2.1        ^^^
+"#,
+        "qux",
+    )
+}
+
+#[test]
+fn acronym() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \newacronym{foo}{FOO}{Foo Bar}
+%1.1             ^^^
+%SRC \acrshort{foo}
+%CUR            ^
+%2.1           ^^^
+"#,
+        "qux",
+    )
+}
@@ -0,0 +1,82 @@
+use lsp_types::{
+    request::SelectionRangeRequest, ClientCapabilities, Position, Range, SelectionRange,
+    SelectionRangeParams, TextDocumentIdentifier,
+};
+
+use crate::tests::{client::Client, fixture};
+
+fn check(fixture: &str, expected_ranges: Vec<(u32, u32, u32, u32)>) {
+    let mut client = Client::spawn();
+    client.initialize(ClientCapabilities::default(), None);
+
+    let fixture = fixture::parse(fixture);
+    for file in fixture.files {
+        client.open(file.name, file.lang, file.text);
+    }
+
+    let cursor = fixture.cursor.unwrap();
+    let mut actual_ranges = client
+        .request::<SelectionRangeRequest>(SelectionRangeParams {
+            text_document: TextDocumentIdentifier::new(client.uri(cursor.name)),
+            positions: vec![cursor.position],
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+        .unwrap()
+        .unwrap_or_default();
+
+    client.shutdown();
+
+    let mut expected_range = None;
+    for (start_line, start_character, end_line, end_character) in expected_ranges.into_iter().rev()
+    {
+        expected_range = Some(SelectionRange {
+            range: Range::new(
+                Position::new(start_line, start_character),
+                Position::new(end_line, end_character),
+            ),
+            parent: expected_range.map(Box::new),
+        });
+    }
+
+    assert_eq!(actual_ranges.len(), 1);
+    assert_eq!(actual_ranges.pop(), expected_range);
+}
+
+#[test]
+fn latex_environment() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \begin{foo}
+%SRC     \bar{baz}
+%CUR          ^
+%SRC     qux
+%SRC \end{foo}
+"#,
+        vec![
+            (1, 9, 1, 12),
+            (1, 8, 1, 13),
+            (1, 4, 1, 13),
+            (1, 4, 2, 7),
+            (0, 0, 3, 9),
+        ],
+    );
+}
+
+#[test]
+fn bibtex_field() {
+    check(
+        r#"
+%BIB main.bib
+%SRC @article{foo, title = {bar baz}}
+%CUR                        ^
+"#,
+        vec![
+            (0, 23, 0, 26),
+            (0, 22, 0, 31),
+            (0, 14, 0, 31),
+            (0, 0, 0, 32),
+        ],
+    );
+}
@@ -0,0 +1,224 @@
+use lsp_types::{
+    request::{SemanticTokensFullRequest, SemanticTokensRangeRequest},
+    ClientCapabilities, Position, Range, SemanticTokenType, SemanticTokens, SemanticTokensParams,
+    SemanticTokensRangeParams, SemanticTokensRangeResult, SemanticTokensResult,
+    SemanticTokensServerCapabilities, TextDocumentIdentifier,
+};
+
+use crate::tests::{client::Client, fixture};
+
+fn decode(tokens: SemanticTokens, legend: &[SemanticTokenType]) -> Vec<(Range, SemanticTokenType)> {
+    let mut results = Vec::new();
+    let mut position = Position::new(0, 0);
+    for token in tokens.data {
+        if token.delta_line == 0 {
+            position.character += token.delta_start;
+        } else {
+            position.line += token.delta_line;
+            position.character = token.delta_start;
+        }
+
+        let end = Position::new(position.line, position.character + token.length);
+        let token_type = legend[token.token_type as usize].clone();
+        results.push((Range::new(position, end), token_type));
+    }
+
+    results
+}
+
+fn check(fixture: &str, viewport: Option<Range>, expected_types: &[SemanticTokenType]) {
+    let mut client = Client::spawn();
+    let result = client.initialize(ClientCapabilities::default(), None);
+    let legend = match result.capabilities.semantic_tokens_provider.unwrap() {
+        SemanticTokensServerCapabilities::SemanticTokensOptions(options) => options.legend,
+        SemanticTokensServerCapabilities::SemanticTokensRegistrationOptions(options) => {
+            options.semantic_tokens_options.legend
+        }
+    };
+
+    let fixture = fixture::parse(fixture);
+    let file_name = fixture.files[0].name;
+    for file in fixture.files {
+        client.open(file.name, file.lang, file.text);
+    }
+
+    let expected_tokens: Vec<_> = fixture
+        .ranges
+        .values()
+        .zip(expected_types)
+        .map(|(ranges, token_type)| (ranges[&1].range, token_type.clone()))
+        .collect();
+
+    let text_document = TextDocumentIdentifier::new(client.uri(file_name));
+    let actual_tokens = match viewport {
+        Some(range) => client
+            .request::<SemanticTokensRangeRequest>(SemanticTokensRangeParams {
+                text_document,
+                range,
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+            .unwrap()
+            .map(|result| match result {
+                SemanticTokensRangeResult::Tokens(tokens) => tokens,
+                SemanticTokensRangeResult::Partial(_) => unreachable!(),
+            }),
+        None => client
+            .request::<SemanticTokensFullRequest>(SemanticTokensParams {
+                text_document,
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+            .unwrap()
+            .map(|result| match result {
+                SemanticTokensResult::Tokens(tokens) => tokens,
+                SemanticTokensResult::Partial(_) => unreachable!(),
+            }),
+    }
+    .map_or_else(Vec::new, |tokens| decode(tokens, &legend.token_types));
+
+    client.shutdown();
+    assert_eq!(actual_tokens, expected_tokens);
+}
+
+#[test]
+fn latex_labels() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \label{foo} \ref{foo}
+%1.1        ^^^
+%2.1                  ^^^
+"#,
+        None,
+        &[SemanticTokenType::VARIABLE, SemanticTokenType::PARAMETER],
+    );
+}
+
+#[test]
+fn latex_citation() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \cite{foo,bar}
+%1.1       ^^^
+%2.1           ^^^
+"#,
+        None,
+        &[
+            SemanticTokenType::ENUM_MEMBER,
+            SemanticTokenType::ENUM_MEMBER,
+        ],
+    );
+}
+
+#[test]
+fn latex_section_and_comment() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \section{Foo} % Bar
+%1.1 ^^^^^^^^
+%2.1               ^^^^^
+"#,
+        None,
+        &[SemanticTokenType::NAMESPACE, SemanticTokenType::COMMENT],
+    );
+}
+
+#[test]
+fn latex_math() {
+    check(
+        r#"
+%TEX main.tex
+%SRC Foo $x$
+%1.1     ^
+%2.1      ^
+%3.1       ^
+"#,
+        None,
+        &[
+            SemanticTokenType::NUMBER,
+            SemanticTokenType::NUMBER,
+            SemanticTokenType::NUMBER,
+        ],
+    );
+}
+
+#[test]
+fn latex_multiline_verbatim() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \begin{verbatim}
+%SRC foo
+%1.1 ^^^
+%SRC bar
+%2.1 ^^^
+%SRC \end{verbatim}
+"#,
+        None,
+        &[SemanticTokenType::STRING, SemanticTokenType::STRING],
+    );
+}
+
+#[test]
+fn latex_range() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \label{foo}
+%SRC \label{bar}
+%1.1        ^^^
+"#,
+        Some(Range::new(Position::new(1, 0), Position::new(1, 11))),
+        &[SemanticTokenType::VARIABLE],
+    );
+}
+
+#[test]
+fn bibtex_entry() {
+    check(
+        r#"
+%BIB main.bib
+%SRC @article{foo, author = bar}
+%1.1 ^^^^^^^^
+%2.1          ^^^
+%3.1               ^^^^^^
+%4.1                        ^^^
+"#,
+        None,
+        &[
+            SemanticTokenType::TYPE,
+            SemanticTokenType::ENUM,
+            SemanticTokenType::PROPERTY,
+            SemanticTokenType::MACRO,
+        ],
+    );
+}
+
+#[test]
+fn bibtex_string() {
+    check(
+        r#"
+%BIB main.bib
+%SRC @string{foo = "bar"}
+%1.1 ^^^^^^^
+%2.1         ^^^
+%SRC @article{baz, title = foo}
+%3.1 ^^^^^^^^
+%4.1          ^^^
+%5.1               ^^^^^
+%6.1                       ^^^
+"#,
+        None,
+        &[
+            SemanticTokenType::TYPE,
+            SemanticTokenType::FUNCTION,
+            SemanticTokenType::TYPE,
+            SemanticTokenType::ENUM,
+            SemanticTokenType::PROPERTY,
+            SemanticTokenType::MACRO,
+        ],
+    );
+}
@@ -0,0 +1,107 @@
+use lsp_types::{request::SignatureHelpRequest, ClientCapabilities, SignatureHelpParams};
+
+use crate::tests::{client::Client, fixture};
+
+fn check(fixture: &str, expected: Option<(&str, u32)>) {
+    let mut client = Client::spawn();
+    client.initialize(ClientCapabilities::default(), None);
+
+    let fixture = fixture::parse(fixture);
+    for file in fixture.files {
+        client.open(file.name, file.lang, file.text);
+    }
+
+    let actual = client
+        .request::<SignatureHelpRequest>(SignatureHelpParams {
+            context: None,
+            text_document_position_params: fixture.cursor.unwrap().into_params(&client),
+            work_done_progress_params: Default::default(),
+        })
+        .unwrap()
+        .map(|help| {
+            let signature = help.signatures.into_iter().next().unwrap();
+            (signature.label, signature.active_parameter.unwrap())
+        });
+
+    client.shutdown();
+
+    let expected = expected.map(|(label, index)| (String::from(label), index));
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn new_command_mandatory() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \newcommand{\foo}[2]{#1 #2}
+%SRC \foo{a}{}
+%CUR         ^
+"#,
+        Some(("\\foo{#1}{#2}", 1)),
+    );
+}
+
+#[test]
+fn new_command_omitted_optional() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \newcommand{\foo}[2][x]{#1 #2}
+%SRC \foo{a}
+%CUR      ^
+"#,
+        Some(("\\foo[#1]{#2}", 1)),
+    );
+}
+
+#[test]
+fn new_command_after_name() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \newcommand{\foo}[2][x]{#1 #2}
+%SRC \foo
+%CUR     ^
+"#,
+        Some(("\\foo[#1]{#2}", 0)),
+    );
+}
+
+#[test]
+fn new_document_command() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \NewDocumentCommand{\foo}{s O{x} m m}{#2 #3 #4}
+%SRC \foo*[y]{a}{}
+%CUR             ^
+"#,
+        Some(("\\foo[#1]{#2}{#3}", 2)),
+    );
+}
+
+#[test]
+fn nested_unknown_command() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \newcommand{\foo}[1]{#1}
+%SRC \foo{\bar{}}
+%CUR           ^
+"#,
+        Some(("\\foo{#1}", 0)),
+    );
+}
+
+#[test]
+fn unknown_command() {
+    check(
+        r#"
+%TEX main.tex
+%SRC \foo{}
+%CUR      ^
+"#,
+        None,
+    );
+}
@@ -1,5 +1,7 @@
+pub mod bibliography;
 pub mod capabilities;
 pub mod chktex;
+pub mod command_signature;
 pub mod components;
 pub mod cursor;
 pub mod label;
@@ -7,6 +9,7 @@ pub mod lang_data;
 pub mod line_index;
 pub mod line_index_ext;
 pub mod lsp_enums;
+pub mod magic_comment;
 
 use std::path::PathBuf;
 
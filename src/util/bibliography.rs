@@ -0,0 +1,26 @@
+use rowan::ast::AstNode;
+
+use crate::syntax::{
+    bibtex::{self, HasName},
+    latex,
+};
+
+/// Finds the keys of all citations in a LaTeX document including the `*` of `\nocite{*}`.
+pub fn citation_keys(root: &latex::SyntaxNode) -> impl Iterator<Item = latex::Key> {
+    root.descendants()
+        .filter_map(latex::Citation::cast)
+        .filter_map(|citation| citation.key_list())
+        .flat_map(|keys| keys.keys())
+}
+
+/// Finds the entries of a BibTeX document together with their keys.
+pub fn entry_keys(
+    root: &bibtex::SyntaxNode,
+) -> impl Iterator<Item = (bibtex::Entry, bibtex::SyntaxToken)> {
+    root.children()
+        .filter_map(bibtex::Entry::cast)
+        .filter_map(|entry| {
+            let key = entry.name_token()?;
+            Some((entry, key))
+        })
+}
@@ -91,6 +91,7 @@ impl Command {
                     severity,
                     code: DiagnosticCode::Chktex(code.into()),
                     message,
+                    related_information: Vec::new(),
                 });
             }
 
@@ -0,0 +1,259 @@
+use std::{iter::Peekable, str::Chars};
+
+use rowan::ast::AstNode;
+
+use crate::syntax::latex::{self, HasBrack, HasCurly};
+
+/// The argument slots of a user-defined command.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CommandSignature {
+    pub name: String,
+    pub arguments: Vec<CommandArgument>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CommandArgument {
+    pub open: char,
+    pub close: char,
+    pub optional: bool,
+    pub default: Option<String>,
+}
+
+impl CommandArgument {
+    pub fn mandatory() -> Self {
+        Self {
+            open: '{',
+            close: '}',
+            optional: false,
+            default: None,
+        }
+    }
+
+    pub fn optional(default: Option<String>) -> Self {
+        Self {
+            open: '[',
+            close: ']',
+            optional: true,
+            default,
+        }
+    }
+}
+
+impl CommandSignature {
+    /// Extracts the signature of `\newcommand` and `\NewDocumentCommand`-like definitions.
+    /// Star and token arguments are not part of the signature since they are matched by the lexer.
+    pub fn parse(definition: &latex::CommandDefinition) -> Option<Self> {
+        let name = definition.name()?.command()?.text().to_string();
+        let arguments = match definition.argument_spec() {
+            Some(spec) => parse_argument_spec(&spec.content_text()?),
+            None => {
+                let count = definition
+                    .parameter_count()
+                    .and_then(|group| group.key())
+                    .and_then(|key| key.to_string().trim().parse::<usize>().ok())
+                    .unwrap_or(0);
+
+                let default = definition
+                    .default_argument()
+                    .and_then(|group| group.content_text());
+
+                let mut arguments = Vec::new();
+                for i in 0..count {
+                    match &default {
+                        Some(default) if i == 0 => {
+                            arguments.push(CommandArgument::optional(Some(default.clone())));
+                        }
+                        _ => arguments.push(CommandArgument::mandatory()),
+                    };
+                }
+
+                arguments
+            }
+        };
+
+        Some(Self { name, arguments })
+    }
+
+    /// Finds the signatures of all user-defined commands with the given name.
+    pub fn find_all(root: &latex::SyntaxNode, name: &str) -> Vec<Self> {
+        let name = name.strip_suffix('*').unwrap_or(name);
+        root.descendants()
+            .filter_map(latex::CommandDefinition::cast)
+            .filter_map(|definition| Self::parse(&definition))
+            .filter(|signature| signature.name == name)
+            .collect()
+    }
+
+    /// Maps the groups of a command call (given by their opening delimiter) to argument slots.
+    /// Omitted optional arguments are skipped.
+    pub fn match_groups(&self, groups: impl IntoIterator<Item = char>) -> Vec<Option<usize>> {
+        let mut index = 0;
+        let mut results = Vec::new();
+        for open in groups {
+            while self
+                .arguments
+                .get(index)
+                .map_or(false, |argument| argument.optional && argument.open != open)
+            {
+                index += 1;
+            }
+
+            if index < self.arguments.len() {
+                results.push(Some(index));
+                index += 1;
+            } else {
+                results.push(None);
+            }
+        }
+
+        results
+    }
+
+    /// Builds a snippet that inserts a tab stop for every mandatory argument
+    /// and the declared default for every optional one.
+    pub fn snippet(&self) -> Option<String> {
+        let mut snippet = String::new();
+        let mut tab_stop = 1;
+        for argument in &self.arguments {
+            let text = match (argument.optional, &argument.default) {
+                (true, None) => continue,
+                (true, Some(default)) => escape_snippet(default),
+                (false, None) => format!("${tab_stop}"),
+                (false, Some(default)) => format!("${{{tab_stop}:{}}}", escape_snippet(default)),
+            };
+
+            if !argument.optional {
+                tab_stop += 1;
+            }
+
+            snippet.push(argument.open);
+            snippet.push_str(&text);
+            snippet.push(argument.close);
+        }
+
+        (!snippet.is_empty()).then_some(snippet)
+    }
+}
+
+fn escape_snippet(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('$', "\\$")
+        .replace('}', "\\}")
+}
+
+fn parse_argument_spec(spec: &str) -> Vec<CommandArgument> {
+    let mut arguments = Vec::new();
+    let mut chars = spec.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            'm' | 'v' => arguments.push(CommandArgument::mandatory()),
+            'o' => arguments.push(CommandArgument::optional(None)),
+            'O' => arguments.push(CommandArgument::optional(read_group(&mut chars))),
+            'r' | 'R' | 'd' | 'D' => {
+                let (open, close) = match (chars.next(), chars.next()) {
+                    (Some(open), Some(close)) => (open, close),
+                    _ => break,
+                };
+
+                let default = if c.is_ascii_uppercase() {
+                    read_group(&mut chars)
+                } else {
+                    None
+                };
+
+                arguments.push(CommandArgument {
+                    open,
+                    close,
+                    optional: matches!(c, 'd' | 'D'),
+                    default,
+                });
+            }
+            't' => {
+                chars.next();
+            }
+            'e' | '>' | '=' => {
+                read_group(&mut chars);
+            }
+            'E' => {
+                read_group(&mut chars);
+                read_group(&mut chars);
+            }
+            _ => {}
+        };
+    }
+
+    arguments
+}
+
+fn read_group(chars: &mut Peekable<Chars>) -> Option<String> {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+    chars.next_if_eq(&'{')?;
+
+    let mut depth = 0;
+    let mut text = String::new();
+    for c in chars.by_ref() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => break,
+            '}' => depth -= 1,
+            _ => {}
+        };
+
+        text.push(c);
+    }
+
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use rowan::ast::AstNode;
+
+    use crate::{parser::parse_latex, syntax::latex};
+
+    use super::CommandSignature;
+
+    fn snippet(text: &str) -> Option<String> {
+        let root = latex::SyntaxNode::new_root(parse_latex(text));
+        let definition = root
+            .descendants()
+            .find_map(latex::CommandDefinition::cast)
+            .unwrap();
+
+        CommandSignature::parse(&definition).unwrap().snippet()
+    }
+
+    #[test]
+    fn test_new_command_mandatory() {
+        assert_eq!(
+            snippet(r"\newcommand{\norm}[1]{\lVert #1 \rVert}"),
+            Some(String::from("{$1}"))
+        );
+    }
+
+    #[test]
+    fn test_new_command_optional() {
+        assert_eq!(
+            snippet(r"\newcommand{\foo}[3][x]{#1 #2 #3}"),
+            Some(String::from("[x]{$1}{$2}"))
+        );
+    }
+
+    #[test]
+    fn test_new_command_without_arguments() {
+        assert_eq!(snippet(r"\newcommand{\foo}{bar}"), None);
+    }
+
+    #[test]
+    fn test_math_operator() {
+        assert_eq!(snippet(r"\DeclareMathOperator{\Tr}{Tr}"), None);
+    }
+
+    #[test]
+    fn test_document_command() {
+        assert_eq!(
+            snippet(r"\NewDocumentCommand{\foo}{s o O{\bar} m R(){y}}{#1}"),
+            Some(String::from(r"[\\bar]{$1}(${2:y})"))
+        );
+    }
+}
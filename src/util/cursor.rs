@@ -151,7 +151,10 @@ impl<'db, T> CursorContext<'db, T> {
                 let right = root.token_at_offset(offset).right_biased();
                 Cursor::new_bib(left, right)
             }
-            DocumentData::Log(_) | DocumentData::TexlabRoot(_) | DocumentData::Tectonic(_) => None,
+            DocumentData::Log(_)
+            | DocumentData::TexlabRoot(_)
+            | DocumentData::Tectonic(_)
+            | DocumentData::Latexmkrc(_) => None,
         };
 
         Some(Self {
@@ -251,6 +254,27 @@ impl<'db, T> CursorContext<'db, T> {
         Some((name, range))
     }
 
+    pub fn find_environment_definition_name(&self) -> Option<(String, TextRange)> {
+        self.find_name_of(|node| {
+            latex::EnvironmentDefinition::cast(node.clone())
+                .and_then(|definition| definition.name())
+                .or_else(|| latex::TheoremDefinition::cast(node)?.name())
+        })
+    }
+
+    /// Finds the name at the cursor if `name` returns its group for the enclosing node.
+    pub fn find_name_of(
+        &self,
+        name: impl FnOnce(latex::SyntaxNode) -> Option<latex::CurlyGroupWord>,
+    ) -> Option<(String, TextRange)> {
+        let (text, range, group) = self.find_curly_group_word()?;
+        if name(group.syntax().parent()?)?.syntax() != group.syntax() {
+            return None;
+        }
+
+        Some((text, range))
+    }
+
     pub fn find_curly_group_word(&self) -> Option<(String, TextRange, latex::CurlyGroupWord)> {
         let token = self.cursor.as_tex()?;
         let key = latex::Key::cast(token.parent()?);
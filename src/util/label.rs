@@ -106,6 +106,23 @@ impl RenderedLabel {
         }
     }
 
+    /// Renders the label like `\cref` or `\autoref` would, e.g. `Figure 3` or `Theorem 2.1`.
+    pub fn short_reference(&self, db: &dyn Db) -> String {
+        let kind = match &self.object {
+            Section { prefix, .. } => *prefix,
+            Float { kind, .. } => kind.as_str(),
+            Theorem { kind, .. } => kind.text(db).as_str(),
+            Equation => "Equation",
+            EnumItem => "Item",
+        };
+
+        match (&self.number, &self.object) {
+            (Some(number), Equation) => format!("{} ({})", kind, number.text(db)),
+            (Some(number), _) => format!("{} {}", kind, number.text(db)),
+            (None, _) => kind.to_string(),
+        }
+    }
+
     pub fn detail(&self, db: &dyn Db) -> Option<String> {
         match &self.object {
             Section { .. } | Theorem { .. } | Equation | EnumItem => Some(self.reference(db)),
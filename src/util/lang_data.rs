@@ -1,4 +1,5 @@
 use once_cell::sync::Lazy;
+use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
@@ -28,16 +29,87 @@ pub struct BibtexFieldDoc {
     pub documentation: String,
 }
 
+/// The fields of an entry type. Alternatives are separated by a slash (e.g. `date/year`).
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BibtexEntryTypeFields {
+    pub required: Vec<String>,
+    #[serde(default)]
+    pub optional: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BibtexDataModel {
+    pub common_fields: Vec<String>,
+    pub entry_types: FxHashMap<String, BibtexEntryTypeFields>,
+}
+
+impl BibtexDataModel {
+    #[must_use]
+    pub fn find_entry_type(&self, name: &str) -> Option<&BibtexEntryTypeFields> {
+        self.entry_types.get(&name.to_lowercase())
+    }
+
+    #[must_use]
+    pub fn is_known_field(&self, entry_type: &BibtexEntryTypeFields, name: &str) -> bool {
+        let name = name.to_lowercase();
+        self.common_fields
+            .iter()
+            .chain(&entry_type.required)
+            .chain(&entry_type.optional)
+            .flat_map(|field| field.split('/'))
+            .any(|field| field == name)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BibtexDataModels {
+    pub bibtex: BibtexDataModel,
+    pub biblatex: BibtexDataModel,
+}
+
+/// A color provided by `xcolor` together with the model and spec it is defined with.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PredefinedColor {
+    pub name: String,
+    pub model: String,
+    pub spec: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum KeyValueContextKind {
+    Package,
+    Class,
+    Command,
+    Environment,
+}
+
+/// The known option keys of a package, class, command or environment.
+/// Every key maps to its allowed values, which may be empty.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyValueContext {
+    pub kind: KeyValueContextKind,
+    pub names: Vec<String>,
+    pub options: FxHashMap<String, Vec<String>>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LanguageData {
-    pub colors: Vec<String>,
+    pub colors: Vec<PredefinedColor>,
     pub entry_types: Vec<BibtexEntryTypeDoc>,
     pub fields: Vec<BibtexFieldDoc>,
     pub pgf_libraries: Vec<String>,
     pub tikz_libraries: Vec<String>,
     pub math_environments: Vec<String>,
     pub enum_environments: Vec<String>,
+    pub data_models: BibtexDataModels,
+    pub key_value_options: Vec<KeyValueContext>,
 }
 
 impl LanguageData {
@@ -62,6 +134,17 @@ impl LanguageData {
             .find(|field| field.name.to_lowercase() == name.to_lowercase())
             .map(|field| field.documentation.as_ref())
     }
+
+    pub fn key_value_options<'a>(
+        &'a self,
+        kind: KeyValueContextKind,
+        name: &'a str,
+    ) -> impl Iterator<Item = (&'a String, &'a Vec<String>)> + 'a {
+        self.key_value_options
+            .iter()
+            .filter(move |context| context.kind == kind && context.names.iter().any(|n| n == name))
+            .flat_map(|context| &context.options)
+    }
 }
 
 pub static LANGUAGE_DATA: Lazy<LanguageData> = Lazy::new(|| {
@@ -24,6 +24,7 @@ pub enum Structure {
     Entry(BibtexEntryTypeCategory),
     Field,
     Argument,
+    OptionKey,
     GlossaryEntry,
 }
 
@@ -56,6 +57,7 @@ impl Structure {
             Self::Entry(BibtexEntryTypeCategory::Thesis) => CompletionItemKind::UNIT,
             Self::Field => CompletionItemKind::FIELD,
             Self::Argument => CompletionItemKind::VALUE,
+            Self::OptionKey => CompletionItemKind::PROPERTY,
             Self::GlossaryEntry => CompletionItemKind::KEYWORD,
         }
     }
@@ -88,6 +90,7 @@ impl Structure {
             Self::Entry(BibtexEntryTypeCategory::Thesis) => SymbolKind::OBJECT,
             Self::Field => SymbolKind::FIELD,
             Self::Argument => SymbolKind::NUMBER,
+            Self::OptionKey => SymbolKind::PROPERTY,
             Self::GlossaryEntry => unimplemented!(),
         }
     }
@@ -0,0 +1,45 @@
+/// Finds the value of a TeXShop-style magic comment like `% !TEX program = xelatex`.
+/// Only the comments at the beginning of the document are considered.
+pub fn find_magic_comment<'a>(text: &'a str, key: &str) -> Option<&'a str> {
+    text.lines()
+        .map(str::trim)
+        .take_while(|line| line.is_empty() || line.starts_with('%'))
+        .filter_map(|line| {
+            let line = line.trim_start_matches('%').trim_start();
+            let line = line
+                .get(..4)
+                .filter(|prefix| prefix.eq_ignore_ascii_case("!TEX"))
+                .map(|_| &line[4..])?
+                .strip_prefix(char::is_whitespace)?;
+
+            let (name, value) = line.split_once('=')?;
+            let name = name.trim();
+            let name = name.strip_prefix("TS-").unwrap_or(name);
+            name.eq_ignore_ascii_case(key).then_some(value.trim())
+        })
+        .find(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find_magic_comment;
+
+    #[test]
+    fn test_program() {
+        let text = "% !TEX program = xelatex\n\\documentclass{article}";
+        assert_eq!(find_magic_comment(text, "program"), Some("xelatex"));
+    }
+
+    #[test]
+    fn test_texshop_program() {
+        let text = "%!TEX TS-program = lualatex\n% !TEX root = main.tex\n";
+        assert_eq!(find_magic_comment(text, "program"), Some("lualatex"));
+        assert_eq!(find_magic_comment(text, "root"), Some("main.tex"));
+    }
+
+    #[test]
+    fn test_after_preamble() {
+        let text = "\\documentclass{article}\n% !TEX program = xelatex";
+        assert_eq!(find_magic_comment(text, "program"), None);
+    }
+}